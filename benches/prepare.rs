@@ -0,0 +1,84 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Benchmarks the public input preparation loop, `vk_ic[0] + sum(input_i * vk_ic[i + 1])`.
+//!
+//! Run with `cargo bench --bench prepare`.
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use risc0_solana::client::PreparedInputCache;
+use risc0_solana::{prepare_public_inputs, PublicInputs, VerificationKey, RISC0_VERIFICATION_KEY};
+
+// Synthetic key for `N` inputs, cycling through the IC points of the RISC Zero key.
+fn synthetic_ic(n: usize) -> Vec<[u8; 64]> {
+    RISC0_VERIFICATION_KEY
+        .vk_ic
+        .iter()
+        .cycle()
+        .take(n + 1)
+        .copied()
+        .collect()
+}
+
+// Distinct scalars well below the field modulus.
+fn synthetic_inputs<const N: usize>() -> PublicInputs<N> {
+    let mut inputs = [[0u8; 32]; N];
+    for (i, input) in inputs.iter_mut().enumerate() {
+        input[1..].fill(0x5a ^ i as u8);
+    }
+    PublicInputs { inputs }
+}
+
+fn bench_n<const N: usize>(c: &mut Criterion) {
+    let ic = synthetic_ic(N);
+    let vk = VerificationKey {
+        nr_pubinputs: N as u32,
+        vk_ic: &ic,
+        ..RISC0_VERIFICATION_KEY
+    };
+    let public = synthetic_inputs::<N>();
+
+    let mut group = c.benchmark_group("prepare");
+    group.bench_with_input(
+        BenchmarkId::new("prepare_public_inputs", N),
+        &public,
+        |b, public| b.iter(|| prepare_public_inputs(black_box(public), &vk).unwrap()),
+    );
+    group.bench_with_input(
+        BenchmarkId::new("PreparedInputCache/cold", N),
+        &public,
+        |b, public| {
+            b.iter(|| {
+                PreparedInputCache::new()
+                    .prepare(black_box(public), &vk)
+                    .unwrap()
+            })
+        },
+    );
+    let mut cache = PreparedInputCache::new();
+    group.bench_with_input(
+        BenchmarkId::new("PreparedInputCache/warm", N),
+        &public,
+        |b, public| b.iter(|| cache.prepare(black_box(public), &vk).unwrap()),
+    );
+    group.finish();
+}
+
+fn bench_prepare(c: &mut Criterion) {
+    bench_n::<5>(c);
+    bench_n::<20>(c);
+}
+
+criterion_group!(benches, bench_prepare);
+criterion_main!(benches);
@@ -70,7 +70,7 @@ async function verify_proof(
   programId: PublicKey
 ): Promise<void> {
   const publicInputsAccountKeypair = Keypair.generate();
-  const space = 160; // 5 * 32 bytes for public inputs
+  const space = 168; // 8-byte discriminator + 5 * 32 bytes for public inputs, see public_inputs_account_size
   const lamports = await connection.getMinimumBalanceForRentExemption(space);
 
   const createAccountInstruction = SystemProgram.createAccount({
@@ -12,8 +12,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use borsh::{BorshDeserialize, BorshSerialize};
-use risc0_solana::{public_inputs, verify_proof, Proof, VerificationKey};
+use risc0_solana::{
+    public_inputs, public_inputs_account_size, verify_proof, Proof, PublicInputsStorage,
+    RISC0_VERIFICATION_KEY,
+};
 use solana_program::entrypoint::ProgramResult;
 use solana_program::{
     account_info::AccountInfo,
@@ -24,9 +26,16 @@ use solana_program::{
 };
 entrypoint!(process_instruction);
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ProofElement {
+    A,
+    B,
+    C,
+}
+
+#[derive(Debug, PartialEq, Eq)]
 enum VerifierProgramError {
-    DecompressionFailure,
+    DecompressionFailure { element: ProofElement },
     VerificationFailure,
 }
 
@@ -36,107 +45,47 @@ const ALLOWED_CONTROL_ROOT: &str =
 const BN254_IDENTITY_CONTROL_ID: &str =
     "4e160df1e119ac0e3d658755a9edf38c8feb307b34bc10b57f4538dbe122a005";
 
-// From risc0-ethereum: https://github.com/risc0/risc0-ethereum/blob/main/contracts/src/groth16/Groth16Verifier.sol
-const VERIFYING_KEY: VerificationKey = VerificationKey {
-    nr_pubinputs: 5,
-    vk_alpha_g1: [
-        45, 77, 154, 167, 227, 2, 217, 223, 65, 116, 157, 85, 7, 148, 157, 5, 219, 234, 51, 251,
-        177, 108, 100, 59, 34, 245, 153, 162, 190, 109, 242, 226, 20, 190, 221, 80, 60, 55, 206,
-        176, 97, 216, 236, 96, 32, 159, 227, 69, 206, 137, 131, 10, 25, 35, 3, 1, 240, 118, 202,
-        255, 0, 77, 25, 38,
-    ],
-    vk_beta_g2: [
-        9, 103, 3, 47, 203, 247, 118, 209, 175, 201, 133, 248, 136, 119, 241, 130, 211, 132, 128,
-        166, 83, 242, 222, 202, 169, 121, 76, 188, 59, 243, 6, 12, 14, 24, 120, 71, 173, 76, 121,
-        131, 116, 208, 214, 115, 43, 245, 1, 132, 125, 214, 139, 192, 224, 113, 36, 30, 2, 19, 188,
-        127, 193, 61, 183, 171, 48, 76, 251, 209, 224, 138, 112, 74, 153, 245, 232, 71, 217, 63,
-        140, 60, 170, 253, 222, 196, 107, 122, 13, 55, 157, 166, 154, 77, 17, 35, 70, 167, 23, 57,
-        193, 177, 164, 87, 168, 199, 49, 49, 35, 210, 77, 47, 145, 146, 248, 150, 183, 198, 62,
-        234, 5, 169, 213, 127, 6, 84, 122, 208, 206, 200,
-    ],
-    vk_gamma_g2: [
-        25, 142, 147, 147, 146, 13, 72, 58, 114, 96, 191, 183, 49, 251, 93, 37, 241, 170, 73, 51,
-        53, 169, 231, 18, 151, 228, 133, 183, 174, 243, 18, 194, 24, 0, 222, 239, 18, 31, 30, 118,
-        66, 106, 0, 102, 94, 92, 68, 121, 103, 67, 34, 212, 247, 94, 218, 221, 70, 222, 189, 92,
-        217, 146, 246, 237, 9, 6, 137, 208, 88, 95, 240, 117, 236, 158, 153, 173, 105, 12, 51, 149,
-        188, 75, 49, 51, 112, 179, 142, 243, 85, 172, 218, 220, 209, 34, 151, 91, 18, 200, 94, 165,
-        219, 140, 109, 235, 74, 171, 113, 128, 141, 203, 64, 143, 227, 209, 231, 105, 12, 67, 211,
-        123, 76, 230, 204, 1, 102, 250, 125, 170,
-    ],
-    vk_delta_g2: [
-        3, 176, 60, 213, 239, 250, 149, 172, 155, 238, 148, 241, 245, 239, 144, 113, 87, 189, 164,
-        129, 44, 207, 11, 76, 145, 244, 43, 182, 41, 248, 58, 28, 26, 160, 133, 255, 40, 23, 154,
-        18, 217, 34, 219, 160, 84, 112, 87, 204, 170, 233, 75, 157, 105, 207, 170, 78, 96, 64, 31,
-        234, 127, 62, 3, 51, 17, 12, 16, 19, 79, 32, 11, 25, 246, 73, 8, 70, 213, 24, 201, 174,
-        168, 104, 54, 110, 251, 114, 40, 202, 92, 145, 210, 148, 13, 3, 7, 98, 30, 96, 243, 31,
-        203, 247, 87, 232, 55, 232, 103, 23, 131, 24, 131, 45, 11, 45, 116, 213, 158, 47, 234, 28,
-        113, 66, 223, 24, 125, 63, 198, 211,
-    ],
-    vk_ic: &[
-        [
-            18, 172, 154, 37, 220, 213, 225, 168, 50, 169, 6, 26, 8, 44, 21, 221, 29, 97, 170, 156,
-            77, 85, 53, 5, 115, 157, 15, 93, 101, 220, 59, 228, 2, 90, 167, 68, 88, 30, 190, 122,
-            217, 23, 49, 145, 28, 137, 133, 105, 16, 111, 245, 162, 211, 15, 62, 238, 43, 35, 198,
-            14, 233, 128, 172, 212,
-        ],
-        [
-            7, 7, 185, 32, 188, 151, 140, 2, 242, 146, 250, 226, 3, 110, 5, 123, 229, 66, 148, 17,
-            76, 204, 60, 135, 105, 216, 131, 246, 136, 161, 66, 63, 46, 50, 160, 148, 183, 88, 149,
-            84, 247, 188, 53, 123, 246, 52, 129, 172, 210, 213, 85, 85, 194, 3, 56, 55, 130, 164,
-            101, 7, 135, 255, 102, 66,
-        ],
-        [
-            11, 202, 54, 226, 203, 230, 57, 75, 62, 36, 151, 81, 133, 63, 150, 21, 17, 1, 28, 113,
-            72, 227, 54, 244, 253, 151, 70, 68, 133, 15, 195, 71, 46, 222, 124, 154, 207, 72, 207,
-            58, 55, 41, 250, 61, 104, 113, 78, 42, 132, 53, 212, 250, 109, 184, 247, 244, 9, 193,
-            83, 177, 252, 223, 155, 139,
-        ],
-        [
-            27, 138, 249, 153, 219, 251, 179, 146, 124, 9, 28, 194, 170, 242, 1, 228, 136, 203,
-            172, 195, 226, 198, 182, 251, 90, 37, 249, 17, 46, 4, 242, 167, 43, 145, 162, 106, 169,
-            46, 27, 111, 87, 34, 148, 159, 25, 42, 129, 200, 80, 213, 134, 216, 26, 96, 21, 127,
-            62, 156, 240, 79, 103, 156, 204, 214,
-        ],
-        [
-            43, 95, 73, 78, 214, 116, 35, 91, 138, 193, 117, 11, 223, 213, 167, 97, 95, 0, 45, 74,
-            29, 206, 254, 221, 208, 110, 218, 90, 7, 108, 205, 13, 47, 229, 32, 173, 32, 32, 170,
-            185, 203, 186, 129, 127, 203, 185, 168, 99, 184, 167, 111, 248, 143, 20, 249, 18, 197,
-            231, 22, 101, 178, 173, 94, 130,
-        ],
-        [
-            15, 28, 60, 13, 93, 157, 160, 250, 3, 102, 104, 67, 205, 228, 232, 46, 134, 155, 165,
-            37, 47, 206, 60, 37, 213, 148, 3, 32, 177, 196, 212, 147, 33, 75, 252, 255, 116, 244,
-            37, 246, 254, 140, 13, 7, 179, 7, 72, 45, 139, 200, 187, 47, 54, 8, 246, 130, 135, 170,
-            1, 189, 11, 105, 232, 9,
-        ],
-    ],
-};
-
-#[derive(BorshSerialize, BorshDeserialize)]
-struct Storage {
-    public_inputs: [[u8; 32]; 5],
-}
+// Written ahead of the stored public inputs so the account can be told apart from other data.
+const STORAGE_DISCRIMINATOR: [u8; 8] = *b"r0pubinp";
 
 #[derive(Debug)]
 enum Instruction {
     VerifyProof,
+    VerifyUncompressedProof,
 }
 
+// Payload lengths, excluding the instruction tag byte.
+// [claim_digest (32 bytes) | compressed_proof_a (32 bytes) | compressed_proof_b (64 bytes) | compressed_proof_c (32 bytes)]
+const COMPRESSED_PAYLOAD_LEN: usize = 32 + Proof::COMPRESSED_LEN;
+// [claim_digest (32 bytes) | proof_a (64 bytes) | proof_b (128 bytes) | proof_c (64 bytes)]
+const UNCOMPRESSED_PAYLOAD_LEN: usize = 32 + Proof::SERIALIZED_LEN;
+
 impl Instruction {
-    fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
-        if input.is_empty() {
-            return Err(ProgramError::InvalidInstructionData);
-        }
-        match input[0] {
-            0 => Ok(Instruction::VerifyProof),
-            _ => Err(ProgramError::InvalidInstructionData),
-        }
+    // Returns the instruction and its payload, i.e. the data after the tag byte.
+    fn unpack(input: &[u8]) -> Result<(Self, &[u8]), ProgramError> {
+        let (tag, payload) = input
+            .split_first()
+            .ok_or(ProgramError::InvalidInstructionData)?;
+        let instruction = match tag {
+            0 => Instruction::VerifyProof,
+            1 => Instruction::VerifyUncompressedProof,
+            _ => return Err(ProgramError::InvalidInstructionData),
+        };
+        Ok((instruction, payload))
     }
 }
 
 impl From<VerifierProgramError> for ProgramError {
     fn from(error: VerifierProgramError) -> Self {
-        ProgramError::Custom(error as u32)
+        // Codes are unchanged from when the element was not reported; it is logged instead.
+        let code = match error {
+            VerifierProgramError::DecompressionFailure { element } => {
+                msg!("Failed to decompress proof element {:?}", element);
+                0
+            }
+            VerifierProgramError::VerificationFailure => 1,
+        };
+        ProgramError::Custom(code)
     }
 }
 
@@ -145,28 +94,54 @@ pub fn process_instruction(
     accounts: &[AccountInfo],
     instruction_data: &[u8],
 ) -> ProgramResult {
-    let instruction = Instruction::unpack(instruction_data)?;
+    let (instruction, payload) = Instruction::unpack(instruction_data)?;
 
     match instruction {
-        Instruction::VerifyProof => verify(accounts, &instruction_data[1..]),
+        Instruction::VerifyProof => verify(accounts, payload),
+        Instruction::VerifyUncompressedProof => verify_uncompressed(accounts, payload),
     }
 }
 
 fn verify(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
-    if accounts.is_empty() {
-        return Err(ProgramError::NotEnoughAccountKeys);
+    if data.len() != COMPRESSED_PAYLOAD_LEN {
+        return Err(ProgramError::InvalidInstructionData);
     }
 
-    let public_inputs_account = &accounts[0];
+    let claim_digest: [u8; 32] = data[..32]
+        .try_into()
+        .map_err(|_| ProgramError::InvalidInstructionData)?;
+    let compressed_proof: &[u8; 128] = data[32..]
+        .try_into()
+        .map_err(|_| ProgramError::InvalidInstructionData)?;
+    let proof = decompress_proof(compressed_proof)?;
+
+    verify_and_store(accounts, claim_digest, &proof)
+}
 
-    // [claim_digest (32 bytes) | compressed_proof_a (32 bytes) | compressed_proof_b (64 bytes) | compressed_proof_c (32 bytes)]
-    if data.len() != 160 {
+// Skips on-chain decompression at the cost of a larger transaction.
+fn verify_uncompressed(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    if data.len() != UNCOMPRESSED_PAYLOAD_LEN {
         return Err(ProgramError::InvalidInstructionData);
     }
 
     let claim_digest: [u8; 32] = data[..32]
         .try_into()
         .map_err(|_| ProgramError::InvalidInstructionData)?;
+    let proof = Proof::try_from(&data[32..])?;
+
+    verify_and_store(accounts, claim_digest, &proof)
+}
+
+fn verify_and_store(
+    accounts: &[AccountInfo],
+    claim_digest: [u8; 32],
+    proof: &Proof,
+) -> ProgramResult {
+    if accounts.is_empty() {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+
+    let public_inputs_account = &accounts[0];
 
     let public_inputs = public_inputs(
         claim_digest,
@@ -174,42 +149,18 @@ fn verify(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
         BN254_IDENTITY_CONTROL_ID,
     )?;
 
-    let stored_public_inputs = Storage {
-        public_inputs: public_inputs.inputs,
-    };
-
-    borsh::to_writer(
-        &mut public_inputs_account.data.borrow_mut()[..],
-        &stored_public_inputs,
-    )?;
+    let stored_public_inputs = PublicInputsStorage::from_public_inputs(&public_inputs);
 
-    // Extract and decompress proof components
-    let compressed_proof_a: &[u8; 32] = data[32..64]
-        .try_into()
-        .map_err(|_| ProgramError::InvalidInstructionData)?;
-    let compressed_proof_b: &[u8; 64] = data[64..128]
-        .try_into()
-        .map_err(|_| ProgramError::InvalidInstructionData)?;
-    let compressed_proof_c: &[u8; 32] = data[128..160]
-        .try_into()
-        .map_err(|_| ProgramError::InvalidInstructionData)?;
-
-    let proof_a = alt_bn128_g1_decompress(compressed_proof_a)
-        .map_err(|_| VerifierProgramError::DecompressionFailure)?;
-
-    let proof_b = alt_bn128_g2_decompress(compressed_proof_b)
-        .map_err(|_| VerifierProgramError::DecompressionFailure)?;
-
-    let proof_c = alt_bn128_g1_decompress(compressed_proof_c)
-        .map_err(|_| VerifierProgramError::DecompressionFailure)?;
-
-    let proof = Proof {
-        pi_a: proof_a,
-        pi_b: proof_b,
-        pi_c: proof_c,
-    };
+    let mut data = public_inputs_account.data.borrow_mut();
+    if data.len() < public_inputs_account_size(5) {
+        return Err(ProgramError::AccountDataTooSmall);
+    }
+    let (discriminator, body) = data.split_at_mut(STORAGE_DISCRIMINATOR.len());
+    discriminator.copy_from_slice(&STORAGE_DISCRIMINATOR);
+    borsh::to_writer(body, &stored_public_inputs)?;
+    drop(data);
 
-    verify_proof(&proof, &public_inputs, &VERIFYING_KEY).map_err(|e| {
+    verify_proof(proof, &public_inputs, &RISC0_VERIFICATION_KEY).map_err(|e| {
         msg!("Proof verification failed: {:?}", e);
         VerifierProgramError::VerificationFailure
     })?;
@@ -219,15 +170,44 @@ fn verify(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
     Ok(())
 }
 
+// Decompresses `compressed_proof_a (32 bytes) | compressed_proof_b (64 bytes) | compressed_proof_c (32 bytes)`.
+fn decompress_proof(data: &[u8; 128]) -> Result<Proof, VerifierProgramError> {
+    let failure = |element| VerifierProgramError::DecompressionFailure { element };
+
+    let compressed_proof_a: &[u8; 32] = data[..32]
+        .try_into()
+        .map_err(|_| failure(ProofElement::A))?;
+    let compressed_proof_b: &[u8; 64] = data[32..96]
+        .try_into()
+        .map_err(|_| failure(ProofElement::B))?;
+    let compressed_proof_c: &[u8; 32] = data[96..]
+        .try_into()
+        .map_err(|_| failure(ProofElement::C))?;
+
+    Ok(Proof {
+        pi_a: alt_bn128_g1_decompress(compressed_proof_a).map_err(|_| failure(ProofElement::A))?,
+        pi_b: alt_bn128_g2_decompress(compressed_proof_b).map_err(|_| failure(ProofElement::B))?,
+        pi_c: alt_bn128_g1_decompress(compressed_proof_c).map_err(|_| failure(ProofElement::C))?,
+    })
+}
+
 #[cfg(test)]
 mod tests {
+    use super::{ProofElement, VerifierProgramError};
     use risc0_solana::client::{compress_g1_be, compress_g2_be, negate_g1};
-    use risc0_solana::{public_inputs, verify_proof, Proof, PublicInputs, VerificationKey};
+    use risc0_solana::{
+        include_vk, public_inputs, public_inputs_account_size, verify_proof, vk_from_json_str,
+        Proof, PublicInputs, VerificationKey,
+    };
     use risc0_zkvm::sha::Digestible;
     use risc0_zkvm::Receipt;
+    use solana_program::account_info::AccountInfo;
     use solana_program::alt_bn128::compression::prelude::{
         alt_bn128_g1_decompress, alt_bn128_g2_decompress,
     };
+    use solana_program::entrypoint::ProgramResult;
+    use solana_program::program_error::ProgramError;
+    use solana_program::pubkey::Pubkey;
 
     // Constants for test data
     const ALLOWED_CONTROL_ROOT: &str =
@@ -280,6 +260,56 @@ mod tests {
         assert!(result.is_ok(), "Proof verification failed");
     }
 
+    #[test]
+    fn test_include_vk() {
+        const EMBEDDED_VK: VerificationKey = include_vk!("../../../test/data/r0_test_vk.json");
+        assert_eq!(EMBEDDED_VK, risc0_solana::RISC0_VERIFICATION_KEY);
+        assert_eq!(EMBEDDED_VK, load_verification_key());
+
+        let (proof, public_inputs) = load_receipt_and_extract_data();
+        assert!(verify_proof(&proof, &public_inputs, &EMBEDDED_VK).is_ok());
+    }
+
+    #[test]
+    fn test_vk_from_json_str() {
+        const INLINE_VK: VerificationKey = vk_from_json_str!(
+            r#"{
+            "protocol": "groth16",
+            "curve": "bn128",
+            "nPublic": 5,
+            "vk_alpha_1": [
+                "20491192805390485299153009773594534940189261866228447918068658471970481763042",
+                "9383485363053290200918347156157836566562967994039712273449902621266178545958",
+                "1"
+            ],
+            "vk_beta_2": [
+                ["6375614351688725206403948262868962793625744043794305715222011528459656738731", "4252822878758300859123897981450591353533073413197771768651442665752259397132"],
+                ["10505242626370262277552901082094356697409835680220590971873171140371331206856", "21847035105528745403288232691147584728191162732299865338377159692350059136679"],
+                ["1", "0"]
+            ],
+            "vk_gamma_2": [
+                ["10857046999023057135944570762232829481370756359578518086990519993285655852781", "11559732032986387107991004021392285783925812861821192530917403151452391805634"],
+                ["8495653923123431417604973247489272438418190587263600148770280649306958101930", "4082367875863433681332203403145435568316851327593401208105741076214120093531"],
+                ["1", "0"]
+            ],
+            "vk_delta_2": [
+                ["12043754404802191763554326994664886008979042643626290185762540825416902247219", "1668323501672964604911431804142266013250380587483576094566949227275849579036"],
+                ["13740680757317479711909903993315946540841369848973133181051452051592786724563", "7710631539206257456743780535472368339139328733484942210876916214502466455394"],
+                ["1", "0"]
+            ],
+            "IC": [
+                ["8446592859352799428420270221449902464741693648963397251242447530457567083492", "1064796367193003797175961162477173481551615790032213185848276823815288302804", "1"],
+                ["3179835575189816632597428042194253779818690147323192973511715175294048485951", "20895841676865356752879376687052266198216014795822152491318012491767775979074", "1"],
+                ["5332723250224941161709478398807683311971555792614491788690328996478511465287", "21199491073419440416471372042641226693637837098357067793586556692319371762571", "1"],
+                ["12457994489566736295787256452575216703923664299075106359829199968023158780583", "19706766271952591897761291684837117091856807401404423804318744964752784280790", "1"],
+                ["19617808913178163826953378459323299110911217259216006187355745713323154132237", "21663537384585072695701846972542344484111393047775983928357046779215877070466", "1"],
+                ["6834578911681792552110317589222010969491336870276623105249474534788043166867", "15060583660288623605191393599883223885678013570733629274538391874953353488393", "1"]
+            ]
+        }"#
+        );
+        assert_eq!(INLINE_VK, load_verification_key());
+    }
+
     #[test]
     fn test_public_inputs_serialization() {
         let (_, public_inputs) = load_receipt_and_extract_data();
@@ -339,6 +369,119 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_decompress_proof_reports_element() {
+        let (proof, _) = load_receipt_and_extract_data();
+        let compressed: [u8; 128] = [
+            compress_g1_be(&proof.pi_a).as_slice(),
+            compress_g2_be(&proof.pi_b).as_slice(),
+            compress_g1_be(&proof.pi_c).as_slice(),
+        ]
+        .concat()
+        .try_into()
+        .unwrap();
+        assert_eq!(super::decompress_proof(&compressed), Ok(proof));
+
+        for (range, element) in [
+            (0..32, ProofElement::A),
+            (32..96, ProofElement::B),
+            (96..128, ProofElement::C),
+        ] {
+            let mut corrupted = compressed;
+            corrupted[range].fill(0xFF);
+            assert_eq!(
+                super::decompress_proof(&corrupted),
+                Err(VerifierProgramError::DecompressionFailure { element })
+            );
+        }
+    }
+
+    fn process_with_storage(instruction_data: &[u8]) -> (ProgramResult, Vec<u8>) {
+        let key = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let mut lamports = 0;
+        let mut data = vec![0u8; public_inputs_account_size(5)];
+        let account = AccountInfo::new(
+            &key,
+            false,
+            true,
+            &mut lamports,
+            &mut data,
+            &owner,
+            false,
+            0,
+        );
+        let result = super::process_instruction(&owner, &[account], instruction_data);
+        (result, data)
+    }
+
+    #[test]
+    fn test_process_compressed_and_uncompressed_proofs() {
+        let (proof, public_inputs) = load_receipt_and_extract_data();
+        let claim_digest = include_bytes!("../../../../test/data/claim_digest.bin");
+
+        let compressed = [
+            &[0u8][..],
+            claim_digest,
+            &compress_g1_be(&proof.pi_a),
+            &compress_g2_be(&proof.pi_b),
+            &compress_g1_be(&proof.pi_c),
+        ]
+        .concat();
+        let uncompressed = [&[1u8][..], claim_digest, &proof.to_bytes()].concat();
+        assert_eq!(compressed.len(), 1 + super::COMPRESSED_PAYLOAD_LEN);
+        assert_eq!(uncompressed.len(), 1 + super::UNCOMPRESSED_PAYLOAD_LEN);
+
+        for instruction_data in [compressed, uncompressed] {
+            let (result, stored) = process_with_storage(&instruction_data);
+            assert_eq!(result, Ok(()));
+            assert_eq!(stored[..8], super::STORAGE_DISCRIMINATOR);
+            assert_eq!(stored[8..], public_inputs.inputs.concat());
+        }
+
+        let mut wrong_proof = proof.clone();
+        wrong_proof.pi_a = negate_g1(&proof.pi_a).unwrap();
+        let instruction_data = [&[1u8][..], claim_digest, &wrong_proof.to_bytes()].concat();
+        assert_eq!(
+            process_with_storage(&instruction_data).0,
+            Err(ProgramError::Custom(1))
+        );
+
+        let truncated = [&[1u8][..], claim_digest, &proof.to_bytes()[..255]].concat();
+        assert_eq!(
+            process_with_storage(&truncated).0,
+            Err(ProgramError::InvalidInstructionData)
+        );
+    }
+
+    #[test]
+    fn test_rejects_short_instruction_data() {
+        assert_eq!(
+            process_with_storage(&[]).0,
+            Err(ProgramError::InvalidInstructionData)
+        );
+        for tag in [0u8, 1] {
+            assert_eq!(
+                process_with_storage(&[tag]).0,
+                Err(ProgramError::InvalidInstructionData)
+            );
+        }
+
+        let short = [0u8; super::COMPRESSED_PAYLOAD_LEN - 1];
+        assert_eq!(
+            super::verify(&[], &short),
+            Err(ProgramError::InvalidInstructionData)
+        );
+        assert_eq!(
+            super::verify(&[], &[]),
+            Err(ProgramError::InvalidInstructionData)
+        );
+        assert_eq!(
+            super::verify_uncompressed(&[], &short),
+            Err(ProgramError::InvalidInstructionData)
+        );
+    }
+
     #[test]
     fn test_negate_g1() {
         let (proof, _) = load_receipt_and_extract_data();
@@ -0,0 +1,156 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Procedural macros for `risc0-solana`. Use them through the re-exports in that crate.
+
+use std::path::Path;
+
+use num_bigint::BigUint;
+use proc_macro::TokenStream;
+use quote::quote;
+use serde_json::Value;
+use syn::{parse_macro_input, LitStr};
+
+/// Embeds a snarkjs `verification_key.json` as a `risc0_solana::VerificationKey<'static>`.
+///
+/// The path is resolved relative to the invoking crate's `CARGO_MANIFEST_DIR`. The key is
+/// converted to the big-endian byte layout at compile time, so it can initialize a `const`.
+///
+/// ```ignore
+/// const VERIFYING_KEY: VerificationKey = include_vk!("verification_key.json");
+/// ```
+#[proc_macro]
+pub fn include_vk(input: TokenStream) -> TokenStream {
+    let path = parse_macro_input!(input as LitStr);
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap_or_default();
+    let full_path = Path::new(&manifest_dir).join(path.value());
+
+    let json = match std::fs::read_to_string(&full_path) {
+        Ok(json) => json,
+        Err(e) => {
+            let msg = format!("Failed to read {}: {}", full_path.display(), e);
+            return syn::Error::new(path.span(), msg).to_compile_error().into();
+        }
+    };
+
+    match expand_vk(&json) {
+        Ok(vk) => {
+            // Referencing the file through `include_str!` makes cargo rebuild on changes.
+            let full_path = full_path.to_string_lossy();
+            quote!({
+                const _: &str = include_str!(#full_path);
+                #vk
+            })
+            .into()
+        }
+        Err(msg) => syn::Error::new(path.span(), msg).to_compile_error().into(),
+    }
+}
+
+/// Converts an inline snarkjs verification key JSON literal into a
+/// `risc0_solana::VerificationKey<'static>` at compile time.
+///
+/// Prefer [`include_vk!`] for keys kept in a file.
+///
+/// ```ignore
+/// const VERIFYING_KEY: VerificationKey = vk_from_json_str!(r#"{ "nPublic": 5, ... }"#);
+/// ```
+#[proc_macro]
+pub fn vk_from_json_str(input: TokenStream) -> TokenStream {
+    let json = parse_macro_input!(input as LitStr);
+    match expand_vk(&json.value()) {
+        Ok(vk) => vk.into(),
+        Err(msg) => syn::Error::new(json.span(), msg).to_compile_error().into(),
+    }
+}
+
+fn expand_vk(json: &str) -> Result<proc_macro2::TokenStream, String> {
+    let json: Value =
+        serde_json::from_str(json).map_err(|e| format!("Invalid verification key JSON: {}", e))?;
+
+    let nr_pubinputs = json["nPublic"]
+        .as_u64()
+        .and_then(|n| u32::try_from(n).ok())
+        .ok_or("Missing or invalid nPublic")?;
+    let alpha = g1_bytes(&json["vk_alpha_1"])?;
+    let beta = g2_bytes(&json["vk_beta_2"])?;
+    let gamma = g2_bytes(&json["vk_gamma_2"])?;
+    let delta = g2_bytes(&json["vk_delta_2"])?;
+    let ic = json["IC"]
+        .as_array()
+        .ok_or("Missing IC")?
+        .iter()
+        .map(g1_bytes)
+        .collect::<Result<Vec<_>, _>>()?;
+    let ic = ic.iter().map(|point| quote!([#(#point),*]));
+
+    Ok(quote! {
+        ::risc0_solana::VerificationKey {
+            nr_pubinputs: #nr_pubinputs,
+            vk_alpha_g1: [#(#alpha),*],
+            vk_beta_g2: [#(#beta),*],
+            vk_gamma_g2: [#(#gamma),*],
+            vk_delta_g2: [#(#delta),*],
+            vk_ic: &[#(#ic),*],
+        }
+    })
+}
+
+// Mirrors `convert_g1` in the core crate: `[x, y, 1]` becomes `x || y`.
+fn g1_bytes(value: &Value) -> Result<Vec<u8>, String> {
+    let coords = value.as_array().ok_or("Invalid G1 point")?;
+    if coords.len() != 3 || decimal(&coords[2])? != BigUint::from(1u8) {
+        return Err("Invalid G1 point: expected [x, y, 1]".to_string());
+    }
+    let mut bytes = be_bytes(&decimal(&coords[0])?)?;
+    bytes.extend(be_bytes(&decimal(&coords[1])?)?);
+    Ok(bytes)
+}
+
+// Mirrors `convert_g2` in the core crate: `[[x0, x1], [y0, y1], [1, 0]]` becomes
+// `x1 || x0 || y1 || y0`.
+fn g2_bytes(value: &Value) -> Result<Vec<u8>, String> {
+    let coords = value.as_array().ok_or("Invalid G2 point")?;
+    let pairs = coords
+        .iter()
+        .map(|pair| match pair.as_array() {
+            Some(pair) if pair.len() == 2 => Ok((decimal(&pair[0])?, decimal(&pair[1])?)),
+            _ => Err("Invalid G2 point structure".to_string()),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    if pairs.len() != 3 || pairs[2] != (BigUint::from(1u8), BigUint::from(0u8)) {
+        return Err("Invalid G2 point: expected [[x0, x1], [y0, y1], [1, 0]]".to_string());
+    }
+
+    let mut bytes = Vec::with_capacity(128);
+    for coord in [&pairs[0].1, &pairs[0].0, &pairs[1].1, &pairs[1].0] {
+        bytes.extend(be_bytes(coord)?);
+    }
+    Ok(bytes)
+}
+
+fn decimal(value: &Value) -> Result<BigUint, String> {
+    let s = value.as_str().ok_or("Expected a decimal string")?;
+    BigUint::parse_bytes(s.as_bytes(), 10).ok_or_else(|| format!("Invalid decimal: {}", s))
+}
+
+fn be_bytes(n: &BigUint) -> Result<Vec<u8>, String> {
+    let bytes = n.to_bytes_be();
+    if bytes.len() > 32 {
+        return Err(format!("Coordinate does not fit in 32 bytes: {}", n));
+    }
+    let mut padded = vec![0u8; 32 - bytes.len()];
+    padded.extend(bytes);
+    Ok(padded)
+}
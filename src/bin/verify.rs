@@ -0,0 +1,58 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use risc0_solana::client::verify_from_files;
+use std::process::ExitCode;
+
+const USAGE: &str = "Usage: verify --vk <VK_JSON> --proof <PROOF_JSON> --public <PUBLIC_JSON>";
+
+fn main() -> ExitCode {
+    let mut vk = None;
+    let mut proof = None;
+    let mut public = None;
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let slot = match arg.as_str() {
+            "--vk" => &mut vk,
+            "--proof" => &mut proof,
+            "--public" => &mut public,
+            "-h" | "--help" => {
+                println!("{USAGE}");
+                return ExitCode::SUCCESS;
+            }
+            _ => {
+                eprintln!("Unknown argument: {arg}\n{USAGE}");
+                return ExitCode::from(2);
+            }
+        };
+        *slot = args.next();
+    }
+
+    let (Some(vk), Some(proof), Some(public)) = (vk, proof, public) else {
+        eprintln!("{USAGE}");
+        return ExitCode::from(2);
+    };
+
+    match verify_from_files(&vk, &proof, &public) {
+        Ok(()) => {
+            println!("Proof verified");
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::FAILURE
+        }
+    }
+}
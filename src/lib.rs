@@ -12,22 +12,73 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use borsh::BorshSerialize;
+use borsh::{BorshDeserialize, BorshSerialize};
 use risc0_zkp::core::digest::Digest;
+use solana_program::account_info::AccountInfo;
+use solana_program::alt_bn128::compression::prelude::{
+    alt_bn128_g1_decompress, alt_bn128_g2_decompress,
+};
 use solana_program::alt_bn128::prelude::{
     alt_bn128_addition, alt_bn128_multiplication, alt_bn128_pairing,
 };
+use solana_program::clock::Clock;
 use solana_program::entrypoint::ProgramResult;
+use solana_program::hash::hashv;
 use solana_program::program_error::ProgramError;
 
+#[cfg(not(target_os = "solana"))]
+use {
+    ark_serialize::{CanonicalDeserialize, Compress, Validate},
+    solana_program::alt_bn128::compression::prelude::convert_endianness,
+};
+
+mod tags;
+mod vk;
+
+pub use risc0_solana_macros::{include_vk, vk_from_json_str};
+pub use tags::{OUTPUT_TAG, RECEIPT_CLAIM_TAG, SYSTEM_STATE_TAG, SYSTEM_STATE_ZERO_DIGEST};
+pub use vk::{
+    DEFAULT_ALLOWED_CONTROL_ROOT, DEFAULT_BN254_IDENTITY_CONTROL_ID, RISC0_VERIFICATION_KEY,
+};
+
+/// Errors returned by the verifier, surfaced on-chain as `ProgramError::Custom(code)`.
+///
+/// The discriminants are stable; match on the `*_CODE` constants rather than hardcoding them.
+///
+/// A proof that fails the pairing check is reported as `PairingMismatch` (code 9). Earlier
+/// releases returned `VerificationError` (code 2) for this case, so callers matching code 2 to
+/// detect an invalid proof must match `PAIRING_MISMATCH_CODE` as well.
 #[derive(Debug)]
+#[repr(u32)]
 pub enum Risc0SolanaError {
-    G1CompressionError,
-    G2CompressionError,
-    VerificationError,
-    InvalidPublicInput,
-    ArithmeticError,
-    PairingError,
+    G1CompressionError = 0,
+    G2CompressionError = 1,
+    VerificationError = 2,
+    InvalidPublicInput = 3,
+    ArithmeticError = 4,
+    PairingError = 5,
+    UnexpectedVerificationKey = 6,
+    OutsideValidityWindow = 7,
+    InvalidVerificationKey = 8,
+    /// The pairing syscall succeeded but the pairing product is not the identity, i.e. the
+    /// proof does not satisfy the verification equation. Syscall failures are `PairingError`.
+    PairingMismatch = 9,
+    /// A G2 point lies on the curve but outside the prime-order subgroup.
+    G2SubgroupError = 10,
+}
+
+impl Risc0SolanaError {
+    pub const G1_COMPRESSION_ERROR_CODE: u32 = Self::G1CompressionError as u32;
+    pub const G2_COMPRESSION_ERROR_CODE: u32 = Self::G2CompressionError as u32;
+    pub const VERIFICATION_ERROR_CODE: u32 = Self::VerificationError as u32;
+    pub const INVALID_PUBLIC_INPUT_CODE: u32 = Self::InvalidPublicInput as u32;
+    pub const ARITHMETIC_ERROR_CODE: u32 = Self::ArithmeticError as u32;
+    pub const PAIRING_ERROR_CODE: u32 = Self::PairingError as u32;
+    pub const UNEXPECTED_VERIFICATION_KEY_CODE: u32 = Self::UnexpectedVerificationKey as u32;
+    pub const OUTSIDE_VALIDITY_WINDOW_CODE: u32 = Self::OutsideValidityWindow as u32;
+    pub const INVALID_VERIFICATION_KEY_CODE: u32 = Self::InvalidVerificationKey as u32;
+    pub const PAIRING_MISMATCH_CODE: u32 = Self::PairingMismatch as u32;
+    pub const G2_SUBGROUP_ERROR_CODE: u32 = Self::G2SubgroupError as u32;
 }
 
 const G1_LEN: usize = 64;
@@ -40,7 +91,11 @@ pub(crate) const BASE_FIELD_MODULUS_Q: [u8; 32] = [
     0x97, 0x81, 0x6A, 0x91, 0x68, 0x71, 0xCA, 0x8D, 0x3C, 0x20, 0x8C, 0x16, 0xD8, 0x7C, 0xFD, 0x47,
 ];
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+/// A Groth16 proof with uncompressed points in big-endian byte order.
+///
+/// G1 points are `x || y` and G2 points are `x_c1 || x_c0 || y_c1 || y_c0`, each coordinate a
+/// 32-byte big-endian integer, matching the layout expected by the `alt_bn128` syscalls.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
 pub struct Proof {
     // NOTE: `pi_a` is expected to be the **negated**
     // version of the proof element.
@@ -63,11 +118,307 @@ pub struct VerificationKey<'a> {
     pub vk_ic: &'a [[u8; G1_LEN]],
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+/// A [`VerificationKey`] that owns its IC points, e.g. after reading it from an account.
+///
+/// The borsh encoding is identical to the one of [`VerificationKey`], so a key serialized
+/// from either type deserializes into this one.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct OwnedVerificationKey {
+    pub nr_pubinputs: u32,
+    pub vk_alpha_g1: [u8; G1_LEN],
+    pub vk_beta_g2: [u8; G2_LEN],
+    pub vk_gamma_g2: [u8; G2_LEN],
+    pub vk_delta_g2: [u8; G2_LEN],
+    pub vk_ic: Vec<[u8; G1_LEN]>,
+}
+
+impl OwnedVerificationKey {
+    /// Borrows the key for use with [`verify_proof`] and friends.
+    pub fn as_ref(&self) -> VerificationKey<'_> {
+        VerificationKey {
+            nr_pubinputs: self.nr_pubinputs,
+            vk_alpha_g1: self.vk_alpha_g1,
+            vk_beta_g2: self.vk_beta_g2,
+            vk_gamma_g2: self.vk_gamma_g2,
+            vk_delta_g2: self.vk_delta_g2,
+            vk_ic: &self.vk_ic,
+        }
+    }
+}
+
+impl From<&VerificationKey<'_>> for OwnedVerificationKey {
+    fn from(vk: &VerificationKey<'_>) -> Self {
+        OwnedVerificationKey {
+            nr_pubinputs: vk.nr_pubinputs,
+            vk_alpha_g1: vk.vk_alpha_g1,
+            vk_beta_g2: vk.vk_beta_g2,
+            vk_gamma_g2: vk.vk_gamma_g2,
+            vk_delta_g2: vk.vk_delta_g2,
+            vk_ic: vk.vk_ic.to_vec(),
+        }
+    }
+}
+
+/// Groth16 public inputs, each a 32-byte **big-endian** scalar.
+///
+/// Use [`PublicInputs::from_le_bytes`] for inputs produced by little-endian tooling.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
 pub struct PublicInputs<const N: usize> {
     pub inputs: [[u8; 32]; N],
 }
 
+/// Account layout for storing verified public inputs, shared by programs and their clients.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct PublicInputsStorage<const N: usize> {
+    pub public_inputs: [[u8; 32]; N],
+}
+
+impl<const N: usize> PublicInputsStorage<N> {
+    pub fn from_public_inputs(public: &PublicInputs<N>) -> Self {
+        PublicInputsStorage {
+            public_inputs: public.inputs,
+        }
+    }
+}
+
+/// A proof together with its RISC Zero public inputs, borsh-encoded as a single blob.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct ProofBundle {
+    pub proof: Proof,
+    pub public_inputs: PublicInputs<5>,
+}
+
+impl<const N: usize> PublicInputs<N> {
+    /// Builds public inputs from little-endian scalars, reversing each into big-endian.
+    pub fn from_le_bytes(inputs: &[[u8; 32]; N]) -> Self {
+        let mut inputs = *inputs;
+        for input in inputs.iter_mut() {
+            input.reverse();
+        }
+        PublicInputs { inputs }
+    }
+
+    /// Heuristic check for public inputs that are almost certainly a construction bug.
+    ///
+    /// Flags inputs that are all zero or all equal, and, for the five RISC Zero inputs, a
+    /// control root half that equals the matching claim digest half. This is not a
+    /// cryptographic check; valid inputs are never rejected by [`verify_proof`] because of it.
+    /// Only compiled in debug builds.
+    #[cfg(debug_assertions)]
+    pub fn looks_malformed(&self) -> bool {
+        if N == 0 {
+            return false;
+        }
+        let all_zero = self.inputs.iter().all(|input| *input == [0u8; 32]);
+        let all_equal = N > 1 && self.inputs.windows(2).all(|w| w[0] == w[1]);
+        let mixed_halves =
+            N == 5 && (self.inputs[0] == self.inputs[2] || self.inputs[1] == self.inputs[3]);
+        all_zero || all_equal || mixed_halves
+    }
+}
+
+impl<'a> VerificationKey<'a> {
+    /// Builds a verification key from big-endian points, e.g. loaded from an account.
+    ///
+    /// Every point must have coordinates below the field modulus, lie on the curve and be in
+    /// the prime-order subgroup, and `ic` must not be empty. Otherwise `InvalidVerificationKey`
+    /// is returned instead of panicking. `nr_pubinputs` is derived from the length of `ic`.
+    ///
+    /// Host-only: validating the G2 points means arkworks subgroup checks executed as plain
+    /// program code rather than syscalls, at an unmeasured cost likely beyond a transaction's
+    /// compute budget. On-chain programs should embed their key as a `const` with
+    /// [`include_vk!`], which does the conversion at compile time.
+    #[cfg(not(target_os = "solana"))]
+    pub fn from_bytes(
+        alpha_g1: &[u8; G1_LEN],
+        beta_g2: &[u8; G2_LEN],
+        gamma_g2: &[u8; G2_LEN],
+        delta_g2: &[u8; G2_LEN],
+        ic: &'a [[u8; G1_LEN]],
+    ) -> Result<Self, Risc0SolanaError> {
+        let nr_pubinputs = ic
+            .len()
+            .checked_sub(1)
+            .and_then(|n| u32::try_from(n).ok())
+            .ok_or(Risc0SolanaError::InvalidVerificationKey)?;
+
+        let g1_valid = |point: &[u8; G1_LEN]| {
+            coordinates_in_field(point)
+                && ark_bn254::G1Affine::deserialize_with_mode(
+                    &convert_endianness::<32, 64>(point)[..],
+                    Compress::No,
+                    Validate::Yes,
+                )
+                .is_ok()
+        };
+        let g2_valid = |point: &[u8; G2_LEN]| {
+            coordinates_in_field(point)
+                && ark_bn254::G2Affine::deserialize_with_mode(
+                    &convert_endianness::<64, 128>(point)[..],
+                    Compress::No,
+                    Validate::Yes,
+                )
+                .is_ok()
+        };
+
+        if !g1_valid(alpha_g1)
+            || ![beta_g2, gamma_g2, delta_g2].into_iter().all(g2_valid)
+            || !ic.iter().all(g1_valid)
+        {
+            return Err(Risc0SolanaError::InvalidVerificationKey);
+        }
+
+        Ok(VerificationKey {
+            nr_pubinputs,
+            vk_alpha_g1: *alpha_g1,
+            vk_beta_g2: *beta_g2,
+            vk_gamma_g2: *gamma_g2,
+            vk_delta_g2: *delta_g2,
+            vk_ic: ic,
+        })
+    }
+
+    /// Returns the SHA-256 digest of the key's borsh encoding.
+    ///
+    /// Programs can store this 32-byte digest instead of the whole key and check keys supplied
+    /// at verification time against it, see [`verify_with_key_and_digest_check`].
+    pub fn digest(&self) -> [u8; 32] {
+        let nr_pubinputs = self.nr_pubinputs.to_le_bytes();
+        let ic_len = (self.vk_ic.len() as u32).to_le_bytes();
+        let mut parts: Vec<&[u8]> = vec![
+            &nr_pubinputs,
+            &self.vk_alpha_g1,
+            &self.vk_beta_g2,
+            &self.vk_gamma_g2,
+            &self.vk_delta_g2,
+            &ic_len,
+        ];
+        parts.extend(self.vk_ic.iter().map(|point| point.as_slice()));
+        hashv(&parts).to_bytes()
+    }
+}
+
+impl PublicInputs<5> {
+    /// Builds the RISC Zero public inputs for a guest run from its image ID and raw journal.
+    ///
+    /// Computes the claim digest of a successful run with [`compute_digests`] and passes it to
+    /// [`public_inputs`] together with the control root and BN254 identity control ID.
+    pub fn from_journal_and_image(
+        image_id: &[u8; 32],
+        journal: &[u8],
+        allowed_control_root: &str,
+        bn254_identity_control_id: &str,
+    ) -> Result<Self, ProgramError> {
+        let (_, claim_digest) = compute_digests(image_id, journal);
+        public_inputs(
+            claim_digest,
+            allowed_control_root,
+            bn254_identity_control_id,
+        )
+    }
+}
+
+impl Proof {
+    /// Size of an uncompressed proof, `pi_a || pi_b || pi_c`, in instruction data.
+    pub const SERIALIZED_LEN: usize = 2 * G1_LEN + G2_LEN;
+    /// Size of a proof with each point compressed to its `x` coordinate and flag bits.
+    pub const COMPRESSED_LEN: usize = Self::SERIALIZED_LEN / 2;
+
+    /// Returns the number of bytes the proof occupies in instruction data.
+    pub const fn byte_len(compressed: bool) -> usize {
+        if compressed {
+            Self::COMPRESSED_LEN
+        } else {
+            Self::SERIALIZED_LEN
+        }
+    }
+
+    /// Splits 256 bytes laid out as `pi_a || pi_b || pi_c`, the inverse of `to_bytes`.
+    pub fn from_bytes(bytes: &[u8; Proof::SERIALIZED_LEN]) -> Proof {
+        let mut proof = Proof {
+            pi_a: [0u8; G1_LEN],
+            pi_b: [0u8; G2_LEN],
+            pi_c: [0u8; G1_LEN],
+        };
+        proof.pi_a.copy_from_slice(&bytes[..64]);
+        proof.pi_b.copy_from_slice(&bytes[64..192]);
+        proof.pi_c.copy_from_slice(&bytes[192..]);
+        proof
+    }
+
+    /// Returns a stable SHA-256 fingerprint of the proof, usable as a deduplication key.
+    ///
+    /// The fingerprint covers `pi_a || pi_b || pi_c`, the same 256 bytes as `to_bytes`.
+    pub fn fingerprint(&self) -> [u8; 32] {
+        hashv(&[&self.pi_a, &self.pi_b, &self.pi_c]).to_bytes()
+    }
+}
+
+/// Parses an untrusted byte slice, e.g. instruction data, returning `InvalidInstructionData`
+/// unless it is exactly 256 bytes.
+impl TryFrom<&[u8]> for Proof {
+    type Error = ProgramError;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        let bytes: &[u8; Proof::SERIALIZED_LEN] = bytes
+            .try_into()
+            .map_err(|_| ProgramError::InvalidInstructionData)?;
+        Ok(Proof::from_bytes(bytes))
+    }
+}
+
+/// Parses `N` concatenated 32-byte big-endian scalars, e.g. from account data.
+///
+/// Returns `InvalidPublicInput` unless `bytes` is exactly `N * 32` bytes long and every scalar
+/// is below the field modulus.
+impl<const N: usize> TryFrom<&[u8]> for PublicInputs<N> {
+    type Error = ProgramError;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        if bytes.len() != N * 32 {
+            return Err(Risc0SolanaError::InvalidPublicInput.into());
+        }
+
+        let mut inputs = [[0u8; 32]; N];
+        for (input, chunk) in inputs.iter_mut().zip(bytes.chunks_exact(32)) {
+            input.copy_from_slice(chunk);
+            if !is_scalar_valid(input) {
+                return Err(Risc0SolanaError::InvalidPublicInput.into());
+            }
+        }
+        Ok(PublicInputs { inputs })
+    }
+}
+
+/// Public inputs whose count is only known at runtime.
+///
+/// Convert into a [`PublicInputs`] with `try_into` to pass them to [`verify_proof`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PublicInputsVec {
+    pub inputs: Vec<[u8; 32]>,
+}
+
+impl<const N: usize> TryFrom<PublicInputsVec> for PublicInputs<N> {
+    type Error = ProgramError;
+
+    fn try_from(public: PublicInputsVec) -> Result<Self, Self::Error> {
+        let inputs = public
+            .inputs
+            .try_into()
+            .map_err(|_| Risc0SolanaError::InvalidPublicInput)?;
+        Ok(PublicInputs { inputs })
+    }
+}
+
+/// Returns the size in bytes of an account storing `n` public inputs.
+///
+/// Reserves 8 bytes for an account discriminator followed by a borsh-encoded
+/// [`PublicInputsStorage`], which is `n` 32-byte inputs. The discriminator is not part of
+/// `PublicInputsStorage`; the program writes it ahead of the storage, as the hello example does.
+pub const fn public_inputs_account_size(n: usize) -> usize {
+    8 + n * 32
+}
+
 impl From<Risc0SolanaError> for ProgramError {
     fn from(error: Risc0SolanaError) -> Self {
         ProgramError::Custom(error as u32)
@@ -83,7 +434,8 @@ impl From<Risc0SolanaError> for ProgramError {
 /// * `vk` - The verification key.
 ///
 /// Note: The proof's `pi_a` element is expected to be the negated version of the proof element.
-/// Ensure that `pi_a` has been negated before calling this function.
+/// Ensure that `pi_a` has been negated before calling this function. A `pi_b` at infinity (all
+/// zero bytes) is rejected with `VerificationError` before any syscall.
 ///
 /// # Returns
 ///
@@ -94,25 +446,260 @@ pub fn verify_proof<const N_PUBLIC: usize>(
     public: &PublicInputs<N_PUBLIC>,
     vk: &VerificationKey,
 ) -> ProgramResult {
-    // Check vk_ic is the correct length
-    if vk.vk_ic.len() != N_PUBLIC + 1 {
+    reject_infinity_pi_b(proof)?;
+    let prepared = prepare_inputs(public, vk, true)?;
+    verify_prepared(proof, &prepared, vk)
+}
+
+/// Verifies a Groth16 proof for keys with many public inputs.
+///
+/// Behaves like [`verify_proof`], but range-checks every input before issuing any syscall so
+/// invalid inputs fail fast, reuses the syscall input buffers across inputs, and skips the
+/// multiplication and addition for zero inputs, which contribute nothing to the sum.
+pub fn verify_proof_large<const N_PUBLIC: usize>(
+    proof: &Proof,
+    public: &PublicInputs<N_PUBLIC>,
+    vk: &VerificationKey,
+) -> ProgramResult {
+    reject_infinity_pi_b(proof)?;
+    if vk.vk_ic.len() != N_PUBLIC + 1 || !public.inputs.iter().all(is_scalar_valid) {
         return Err(Risc0SolanaError::InvalidPublicInput.into());
     }
-    // Prepare public inputs
-    let mut prepared = vk.vk_ic[0];
-    for (i, input) in public.inputs.iter().enumerate() {
-        if !is_scalar_valid(input) {
-            return Err(Risc0SolanaError::InvalidPublicInput.into());
+
+    // `mul_input` holds `point || scalar`, `add_input` holds `accumulator || product`.
+    let mut mul_input = [0u8; G1_LEN + 32];
+    let mut add_input = [0u8; 2 * G1_LEN];
+    add_input[..G1_LEN].copy_from_slice(&vk.vk_ic[0]);
+
+    for (point, input) in vk.vk_ic[1..].iter().zip(public.inputs.iter()) {
+        if *input == [0u8; 32] {
+            continue;
         }
-        let mul_res = alt_bn128_multiplication(&[&vk.vk_ic[i + 1][..], &input[..]].concat())
+        mul_input[..G1_LEN].copy_from_slice(point);
+        mul_input[G1_LEN..].copy_from_slice(input);
+        let product: [u8; G1_LEN] = alt_bn128_multiplication(&mul_input)
+            .map_err(|_| Risc0SolanaError::ArithmeticError)?
+            .try_into()
             .map_err(|_| Risc0SolanaError::ArithmeticError)?;
-        prepared = alt_bn128_addition(&[&mul_res[..], &prepared[..]].concat())
-            .unwrap()
+
+        add_input[G1_LEN..].copy_from_slice(&product);
+        let sum: [u8; G1_LEN] = alt_bn128_addition(&add_input)
+            .map_err(|_| Risc0SolanaError::ArithmeticError)?
             .try_into()
             .map_err(|_| Risc0SolanaError::ArithmeticError)?;
+        add_input[..G1_LEN].copy_from_slice(&sum);
+    }
+
+    let prepared: [u8; G1_LEN] = add_input[..G1_LEN]
+        .try_into()
+        .map_err(|_| Risc0SolanaError::ArithmeticError)?;
+    verify_prepared(proof, &prepared, vk)
+}
+
+// Syscall costs in compute units, from `ComputeBudget::default()` in Solana 1.18:
+// https://github.com/solana-labs/solana/blob/v1.18.20/program-runtime/src/compute_budget.rs
+pub const ALT_BN128_ADDITION_CU: u64 = 334;
+pub const ALT_BN128_MULTIPLICATION_CU: u64 = 3_840;
+pub const ALT_BN128_PAIRING_FIRST_PAIR_CU: u64 = 36_364;
+pub const ALT_BN128_PAIRING_OTHER_PAIR_CU: u64 = 12_121;
+// Allowance for everything besides the syscalls: scalar checks, buffer copies, logging.
+pub const VERIFY_OVERHEAD_CU: u64 = 10_000;
+
+/// Returns a conservative estimate of the compute units [`verify_proof`] consumes for a key
+/// with `n_public` inputs, for use with `ComputeBudgetInstruction::set_compute_unit_limit`.
+///
+/// Each input costs one multiplication and one addition, and the pairing check always covers
+/// four pairs. Update the `*_CU` constants above if Solana changes syscall pricing.
+pub const fn estimated_compute_units(n_public: usize) -> u64 {
+    let per_input = ALT_BN128_MULTIPLICATION_CU + ALT_BN128_ADDITION_CU;
+    let pairing = ALT_BN128_PAIRING_FIRST_PAIR_CU + 3 * ALT_BN128_PAIRING_OTHER_PAIR_CU;
+    n_public as u64 * per_input + pairing + VERIFY_OVERHEAD_CU
+}
+
+/// Computes the prepared public input `vk_ic[0] + sum(input_i * vk_ic[i + 1])`.
+///
+/// Programs whose public inputs stay fixed across transactions can compute this once and cache
+/// it to save the `alt_bn128_multiplication` calls. Inputs are range-checked as in
+/// [`verify_proof`], returning `InvalidPublicInput` for out-of-range scalars.
+pub fn prepare_public_inputs<const N: usize>(
+    public: &PublicInputs<N>,
+    vk: &VerificationKey,
+) -> Result<[u8; G1_LEN], Risc0SolanaError> {
+    prepare_inputs(public, vk, true)
+}
+
+/// Verifies a Groth16 proof without checking that the public inputs are valid scalars.
+///
+/// **Unsound for untrusted inputs**: an input `>= q` aliases a different field element, so a
+/// prover could satisfy the pairing check for inputs the caller never intended. Only use this
+/// when every input was derived by the program itself, e.g. by [`public_inputs`] from a control
+/// root and claim digest it trusts. Otherwise it behaves exactly like [`verify_proof`].
+pub fn verify_proof_trusted<const N_PUBLIC: usize>(
+    proof: &Proof,
+    public: &PublicInputs<N_PUBLIC>,
+    vk: &VerificationKey,
+) -> ProgramResult {
+    reject_infinity_pi_b(proof)?;
+    let prepared = prepare_inputs(public, vk, false)?;
+    verify_prepared(proof, &prepared, vk)
+}
+
+/// Error returned by [`verify_proofs_batched`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BatchError {
+    /// Index of the first proof found invalid, if the failure could be attributed to one.
+    pub index: Option<usize>,
+    pub error: ProgramError,
+}
+
+impl From<BatchError> for ProgramError {
+    fn from(error: BatchError) -> Self {
+        error.error
+    }
+}
+
+/// Verifies several Groth16 proofs against one verification key with a single pairing.
+///
+/// Each proof's pairing terms are scaled by an independent 128-bit scalar derived from a hash of
+/// the key and every proof and input in the batch, so invalid proofs cannot cancel each other
+/// out. The combined check needs `batch.len() + 3` pairings instead of `4 * batch.len()`.
+///
+/// If the combined check fails, proofs are re-checked one by one and the index of the first
+/// invalid proof is reported. As for [`verify_proof`], every `pi_a` must already be negated.
+pub fn verify_proofs_batched<const N_PUBLIC: usize>(
+    batch: &[(&Proof, &PublicInputs<N_PUBLIC>)],
+    vk: &VerificationKey,
+) -> Result<(), BatchError> {
+    let at = |index: usize| {
+        move |error: Risc0SolanaError| BatchError {
+            index: Some(index),
+            error: error.into(),
+        }
+    };
+    let unattributed = |error: Risc0SolanaError| BatchError {
+        index: None,
+        error: error.into(),
+    };
+
+    if batch.is_empty() {
+        return Ok(());
+    }
+
+    let prepared = batch
+        .iter()
+        .enumerate()
+        .map(|(i, (proof, public))| {
+            reject_infinity_pi_b(proof).map_err(at(i))?;
+            prepare_inputs(public, vk, true).map_err(at(i))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let vk_digest = vk.digest();
+    let mut transcript: Vec<&[u8]> = vec![&vk_digest];
+    for (proof, public) in batch {
+        transcript.extend([&proof.pi_a[..], &proof.pi_b[..], &proof.pi_c[..]]);
+        transcript.extend(public.inputs.iter().map(|input| input.as_slice()));
+    }
+    let seed = hashv(&transcript).to_bytes();
+
+    let mut pairing_input = Vec::with_capacity((batch.len() + 3) * (G1_LEN + G2_LEN));
+    let mut prepared_sum = [0u8; G1_LEN];
+    let mut pi_c_sum = [0u8; G1_LEN];
+    let (mut scalar_sum_lo, mut scalar_sum_hi) = (0u128, 0u128);
+
+    for (i, ((proof, _), prepared)) in batch.iter().zip(&prepared).enumerate() {
+        let digest = hashv(&[&seed, &(i as u64).to_le_bytes()]).to_bytes();
+        let r = u128::from_be_bytes(digest[..16].try_into().unwrap());
+        let mut scalar = [0u8; 32];
+        scalar[16..].copy_from_slice(&r.to_be_bytes());
+
+        pairing_input.extend(g1_mul(&proof.pi_a, &scalar).map_err(at(i))?);
+        pairing_input.extend(proof.pi_b);
+        prepared_sum =
+            g1_add(&prepared_sum, &g1_mul(prepared, &scalar).map_err(at(i))?).map_err(at(i))?;
+        pi_c_sum =
+            g1_add(&pi_c_sum, &g1_mul(&proof.pi_c, &scalar).map_err(at(i))?).map_err(at(i))?;
+
+        let (lo, carry) = scalar_sum_lo.overflowing_add(r);
+        scalar_sum_lo = lo;
+        scalar_sum_hi += carry as u128;
+    }
+
+    let mut scalar_sum = [0u8; 32];
+    scalar_sum[..16].copy_from_slice(&scalar_sum_hi.to_be_bytes());
+    scalar_sum[16..].copy_from_slice(&scalar_sum_lo.to_be_bytes());
+
+    pairing_input.extend(prepared_sum);
+    pairing_input.extend(vk.vk_gamma_g2);
+    pairing_input.extend(pi_c_sum);
+    pairing_input.extend(vk.vk_delta_g2);
+    pairing_input.extend(g1_mul(&vk.vk_alpha_g1, &scalar_sum).map_err(unattributed)?);
+    pairing_input.extend(vk.vk_beta_g2);
+
+    let pairing_res = alt_bn128_pairing(&pairing_input)
+        .map_err(|_| unattributed(Risc0SolanaError::PairingError))?;
+    let mut expected = [0u8; 32];
+    expected[31] = 1;
+    if pairing_res == expected {
+        return Ok(());
+    }
+
+    for (i, ((proof, _), prepared)) in batch.iter().zip(&prepared).enumerate() {
+        verify_prepared(proof, prepared, vk).map_err(|error| BatchError {
+            index: Some(i),
+            error,
+        })?;
+    }
+    Err(unattributed(Risc0SolanaError::PairingMismatch))
+}
+
+fn g1_mul(point: &[u8; G1_LEN], scalar: &[u8; 32]) -> Result<[u8; G1_LEN], Risc0SolanaError> {
+    alt_bn128_multiplication(&[&point[..], &scalar[..]].concat())
+        .map_err(|_| Risc0SolanaError::ArithmeticError)?
+        .try_into()
+        .map_err(|_| Risc0SolanaError::ArithmeticError)
+}
+
+fn g1_add(a: &[u8; G1_LEN], b: &[u8; G1_LEN]) -> Result<[u8; G1_LEN], Risc0SolanaError> {
+    alt_bn128_addition(&[&a[..], &b[..]].concat())
+        .map_err(|_| Risc0SolanaError::ArithmeticError)?
+        .try_into()
+        .map_err(|_| Risc0SolanaError::ArithmeticError)
+}
+
+// Computes `vk_ic[0] + sum(vk_ic[i + 1] * input_i)`.
+fn prepare_inputs<const N_PUBLIC: usize>(
+    public: &PublicInputs<N_PUBLIC>,
+    vk: &VerificationKey,
+    check_scalars: bool,
+) -> Result<[u8; G1_LEN], Risc0SolanaError> {
+    // Check vk_ic is the correct length
+    if vk.vk_ic.len() != N_PUBLIC + 1 {
+        return Err(Risc0SolanaError::InvalidPublicInput);
+    }
+
+    let mut prepared = vk.vk_ic[0];
+    for (i, input) in public.inputs.iter().enumerate() {
+        if check_scalars && !is_scalar_valid(input) {
+            return Err(Risc0SolanaError::InvalidPublicInput);
+        }
+        prepared = g1_add(&g1_mul(&vk.vk_ic[i + 1], input)?, &prepared)?;
+    }
+
+    Ok(prepared)
+}
+
+// A `pi_b` at infinity zeroes the `e(A, B)` term, leaving an equation that says nothing about
+// the witness. Rejecting it up front also skips the input preparation syscalls.
+fn reject_infinity_pi_b(proof: &Proof) -> Result<(), Risc0SolanaError> {
+    if proof.pi_b == [0u8; G2_LEN] {
+        return Err(Risc0SolanaError::VerificationError);
     }
+    Ok(())
+}
 
-    // Perform pairing check
+// Performs the pairing check for a proof against already prepared public inputs.
+fn verify_prepared(proof: &Proof, prepared: &[u8; G1_LEN], vk: &VerificationKey) -> ProgramResult {
     let pairing_input = [
         proof.pi_a.as_slice(),
         proof.pi_b.as_slice(),
@@ -138,60 +725,479 @@ pub fn verify_proof<const N_PUBLIC: usize>(
     expected[31] = 1;
 
     if pairing_res != expected {
-        return Err(Risc0SolanaError::VerificationError.into());
+        return Err(Risc0SolanaError::PairingMismatch.into());
     }
 
     Ok(())
 }
 
-pub fn public_inputs(
+/// Like [`verify_proof`], but logs the index of every out-of-range public input via `msg!`
+/// before returning `InvalidPublicInput`, instead of stopping at the first one.
+///
+/// Intended for diagnostic builds only; the extra logging costs compute units.
+#[cfg(feature = "diagnostic")]
+pub fn verify_proof_diagnostic<const N_PUBLIC: usize>(
+    proof: &Proof,
+    public: &PublicInputs<N_PUBLIC>,
+    vk: &VerificationKey,
+) -> ProgramResult {
+    let invalid = invalid_public_input_indices(public);
+    if !invalid.is_empty() {
+        for index in &invalid {
+            solana_program::msg!("Public input {} is not a valid scalar", index);
+        }
+        return Err(Risc0SolanaError::InvalidPublicInput.into());
+    }
+    verify_proof(proof, public, vk)
+}
+
+/// Returns the indices of all public inputs that are not below the field modulus.
+#[cfg(feature = "diagnostic")]
+pub fn invalid_public_input_indices<const N_PUBLIC: usize>(
+    public: &PublicInputs<N_PUBLIC>,
+) -> Vec<usize> {
+    public
+        .inputs
+        .iter()
+        .enumerate()
+        .filter(|(_, input)| !is_scalar_valid(input))
+        .map(|(i, _)| i)
+        .collect()
+}
+
+/// Verifies a proof for `claim_digest` and returns the public inputs derived for it.
+///
+/// Useful for audit logs that need the exact inputs the proof was checked against.
+pub fn verify_proof_logged(
+    proof: &Proof,
     claim_digest: [u8; 32],
     allowed_control_root: &str,
     bn254_identity_control_id: &str,
+    vk: &VerificationKey,
 ) -> Result<PublicInputs<5>, ProgramError> {
-    let allowed_control_root: Digest = digest_from_hex(allowed_control_root);
-    let bn254_identity_control_id: Digest = digest_from_hex(bn254_identity_control_id);
-
-    let (a0, a1) =
-        split_digest_bytes(allowed_control_root).map_err(|_| ProgramError::InvalidAccountData)?;
-    let (c0, c1) = split_digest_bytes(Digest::from(claim_digest))
-        .map_err(|_| ProgramError::InvalidAccountData)?;
+    let public = public_inputs(
+        claim_digest,
+        allowed_control_root,
+        bn254_identity_control_id,
+    )?;
+    verify_proof(proof, &public, vk)?;
+    Ok(public)
+}
 
-    let mut id_bn554 = bn254_identity_control_id.as_bytes().to_vec();
-    id_bn554.reverse();
-    let id_bn254_fr = to_fixed_array(&id_bn554);
+/// Verifies a proof for a precomputed `claim_digest`.
+///
+/// Builds the public inputs from the digest with [`DEFAULT_ALLOWED_CONTROL_ROOT`] and
+/// [`DEFAULT_BN254_IDENTITY_CONTROL_ID`], so programs that already stored the claim digest
+/// don't recompute it from the image ID and journal.
+pub fn verify_proof_with_claim(
+    proof: &Proof,
+    claim_digest: [u8; 32],
+    vk: &VerificationKey,
+) -> ProgramResult {
+    let public = public_inputs(
+        claim_digest,
+        DEFAULT_ALLOWED_CONTROL_ROOT,
+        DEFAULT_BN254_IDENTITY_CONTROL_ID,
+    )?;
+    verify_proof(proof, &public, vk)
+}
 
-    let inputs = [a0, a1, c0, c1, id_bn254_fr];
+/// Verifies that a proof attests to `image_id` committing exactly `expected_journal`.
+///
+/// The claim digest is derived from the journal as in [`compute_digests`] and checked with
+/// [`verify_proof_with_claim`], so a proof produced for any other journal fails verification.
+pub fn verify_with_expected_journal(
+    proof: &Proof,
+    image_id: &[u8; 32],
+    expected_journal: &[u8],
+    vk: &VerificationKey,
+) -> ProgramResult {
+    let (_, claim_digest) = compute_digests(image_id, expected_journal);
+    verify_proof_with_claim(proof, claim_digest, vk)
+}
 
-    Ok(PublicInputs { inputs })
+/// Verifies a proof for `claim_digest` under any of several identity control IDs.
+///
+/// During a RISC Zero upgrade window proofs may be produced against either the old or the new
+/// identity control ID. Each ID is tried in order, returning on the first success, otherwise
+/// the error of the last attempt. An empty `identity_ids` is rejected with
+/// `InvalidPublicInput`.
+pub fn verify_proof_any_identity(
+    proof: &Proof,
+    claim_digest: [u8; 32],
+    allowed_control_root: &str,
+    identity_ids: &[&str],
+    vk: &VerificationKey,
+) -> ProgramResult {
+    let mut result = Err(Risc0SolanaError::InvalidPublicInput.into());
+    for identity_id in identity_ids {
+        let public = public_inputs(claim_digest, allowed_control_root, identity_id)?;
+        result = verify_proof(proof, &public, vk);
+        if result.is_ok() {
+            break;
+        }
+    }
+    result
 }
 
-fn digest_from_hex(hex_str: &str) -> Digest {
-    let bytes = hex::decode(hex_str).expect("Invalid hex string");
-    Digest::from_bytes(bytes.try_into().expect("Invalid digest length"))
+/// Verifies a proof against public inputs assembled from pre-split components.
+///
+/// Each component must already be in the big-endian form [`public_inputs`] produces: the two
+/// halves of the control root, the two halves of the claim digest, and the BN254 identity
+/// control ID. Lets callers that keep the split around skip the digest parsing.
+pub fn verify_with_split_claim(
+    proof: &Proof,
+    control_split: ([u8; 32], [u8; 32]),
+    claim_split: ([u8; 32], [u8; 32]),
+    identity_id: [u8; 32],
+    vk: &VerificationKey,
+) -> ProgramResult {
+    let public = PublicInputs {
+        inputs: [
+            control_split.0,
+            control_split.1,
+            claim_split.0,
+            claim_split.1,
+            identity_id,
+        ],
+    };
+    verify_proof(proof, &public, vk)
 }
 
-fn split_digest_bytes(d: Digest) -> Result<([u8; 32], [u8; 32]), anyhow::Error> {
-    let big_endian: Vec<u8> = d.as_bytes().iter().rev().copied().collect();
-    let middle = big_endian.len() / 2;
-    let (b, a) = big_endian.split_at(middle);
-    Ok((to_fixed_array(a), to_fixed_array(b)))
+/// Verifies a borsh-encoded [`ProofBundle`].
+///
+/// Lets instruction data carry the proof and its public inputs as one argument. Returns
+/// `InvalidInstructionData` if `data` is not a valid bundle.
+pub fn verify_blob(data: &[u8], vk: &VerificationKey) -> ProgramResult {
+    let bundle =
+        ProofBundle::try_from_slice(data).map_err(|_| ProgramError::InvalidInstructionData)?;
+    verify_proof(&bundle.proof, &bundle.public_inputs, vk)
 }
 
-fn to_fixed_array(input: &[u8]) -> [u8; 32] {
-    assert!(input.len() <= 32, "Input length must not exceed 32 bytes");
+// Layout of an account read by [`verify_from_packed_account`]:
+// [compressed_proof_a (32 bytes) | compressed_proof_b (64 bytes) | compressed_proof_c (32 bytes) | public_inputs (5 * 32 bytes)]
+pub const PACKED_PROOF_OFFSET: usize = 0;
+pub const PACKED_PUBLIC_INPUTS_OFFSET: usize = PACKED_PROOF_OFFSET + Proof::COMPRESSED_LEN;
+pub const PACKED_ACCOUNT_LEN: usize = PACKED_PUBLIC_INPUTS_OFFSET + 5 * 32;
 
-    let mut fixed_array = [0u8; 32];
-    let start_index = 32 - input.len();
+/// Verifies a compressed proof stored together with its five public inputs in one account.
+///
+/// The account data must start with the layout described by [`PACKED_PROOF_OFFSET`],
+/// [`PACKED_PUBLIC_INPUTS_OFFSET`] and [`PACKED_ACCOUNT_LEN`]; trailing bytes are ignored.
+/// Returns `InvalidAccountData` if the account is too small, and `G1CompressionError` or
+/// `G2CompressionError` if a point fails to decompress.
+pub fn verify_from_packed_account(account: &AccountInfo, vk: &VerificationKey) -> ProgramResult {
+    let data = account.try_borrow_data()?;
+    let data = data
+        .get(..PACKED_ACCOUNT_LEN)
+        .ok_or(ProgramError::InvalidAccountData)?;
+
+    let proof = decompress_proof(&data[PACKED_PROOF_OFFSET..PACKED_PUBLIC_INPUTS_OFFSET])?;
+    let public = PublicInputs::<5>::try_from(&data[PACKED_PUBLIC_INPUTS_OFFSET..])?;
+
+    verify_proof(&proof, &public, vk)
+}
 
-    fixed_array[start_index..].copy_from_slice(input);
-    fixed_array
+// Decompresses `compressed_a (32 bytes) | compressed_b (64 bytes) | compressed_c (32 bytes)`.
+fn decompress_proof(data: &[u8]) -> Result<Proof, Risc0SolanaError> {
+    let g1 = |bytes: &[u8]| {
+        alt_bn128_g1_decompress(bytes).map_err(|_| Risc0SolanaError::G1CompressionError)
+    };
+    Ok(Proof {
+        pi_a: g1(&data[..32])?,
+        pi_b: alt_bn128_g2_decompress(&data[32..96])
+            .map_err(|_| Risc0SolanaError::G2CompressionError)?,
+        pi_c: g1(&data[96..128])?,
+    })
 }
 
-fn is_scalar_valid(scalar: &[u8; 32]) -> bool {
-    for (s_byte, q_byte) in scalar.iter().zip(BASE_FIELD_MODULUS_Q.iter()) {
-        match s_byte.cmp(q_byte) {
-            std::cmp::Ordering::Less => return true,     // scalar < q
+/// Decompresses a big-endian G2 point and checks that it is in the prime-order subgroup.
+///
+/// The decompression syscall only guarantees the point is on the curve, but BN254 G2 has a
+/// cofactor, so untrusted points may lie outside the subgroup the pairing is defined on. Returns
+/// `G2CompressionError` if decompression fails and `G2SubgroupError` for such a point. The
+/// all-zero encoding decompresses to the point at infinity, which is accepted.
+///
+/// Host-only. The subgroup check runs a G2 scalar multiplication in arkworks field arithmetic,
+/// with no syscall behind it. Its compute unit cost has not been measured and is expected to be
+/// far above a transaction's budget, so on-chain programs must not call it in the verify path.
+#[cfg(not(target_os = "solana"))]
+pub fn decompress_g2_checked(compressed: &[u8; 64]) -> Result<[u8; G2_LEN], Risc0SolanaError> {
+    let point =
+        alt_bn128_g2_decompress(compressed).map_err(|_| Risc0SolanaError::G2CompressionError)?;
+    if point == [0u8; G2_LEN] {
+        return Ok(point);
+    }
+
+    // Validation re-checks the curve equation, which already holds, and the subgroup.
+    let in_subgroup = ark_bn254::G2Affine::deserialize_with_mode(
+        &convert_endianness::<64, 128>(&point)[..],
+        Compress::No,
+        Validate::Yes,
+    )
+    .is_ok();
+    if !in_subgroup {
+        return Err(Risc0SolanaError::G2SubgroupError);
+    }
+    Ok(point)
+}
+
+/// Verifies a Groth16 proof, optionally pinning the verification key.
+///
+/// When `pin_r0_key` is set, `vk` must be exactly [`RISC0_VERIFICATION_KEY`], otherwise
+/// `UnexpectedVerificationKey` is returned before any verification work is done. This guards
+/// programs that bake in the standard key against accidentally loading a different one.
+pub fn verify_proof_pinned<const N_PUBLIC: usize>(
+    proof: &Proof,
+    public: &PublicInputs<N_PUBLIC>,
+    vk: &VerificationKey,
+    pin_r0_key: bool,
+) -> ProgramResult {
+    if pin_r0_key && *vk != RISC0_VERIFICATION_KEY {
+        return Err(Risc0SolanaError::UnexpectedVerificationKey.into());
+    }
+    verify_proof(proof, public, vk)
+}
+
+/// Verifies a Groth16 proof with a caller-supplied key bound to a stored digest.
+///
+/// `vk` is only used if its [`VerificationKey::digest`] equals `expected_digest`, otherwise
+/// `UnexpectedVerificationKey` is returned. This lets a program upgrade keys by updating the
+/// stored digest without embedding the key itself.
+pub fn verify_with_key_and_digest_check<const N_PUBLIC: usize>(
+    proof: &Proof,
+    public: &PublicInputs<N_PUBLIC>,
+    vk: &VerificationKey,
+    expected_digest: &[u8; 32],
+) -> ProgramResult {
+    if vk.digest() != *expected_digest {
+        return Err(Risc0SolanaError::UnexpectedVerificationKey.into());
+    }
+    verify_proof(proof, public, vk)
+}
+
+/// Checks that a timestamp committed in a proof's journal is recent.
+///
+/// Succeeds if `journal_timestamp` (unix seconds) is no more than `max_age` seconds before the
+/// cluster time in `clock`. Timestamps in the future are rejected as well. Decoding the
+/// timestamp from the journal is left to the caller, since the journal layout is guest-specific.
+pub fn assert_within_window(journal_timestamp: i64, max_age: i64, clock: &Clock) -> ProgramResult {
+    let age = clock
+        .unix_timestamp
+        .checked_sub(journal_timestamp)
+        .ok_or(Risc0SolanaError::ArithmeticError)?;
+    if age < 0 || age > max_age {
+        return Err(Risc0SolanaError::OutsideValidityWindow.into());
+    }
+    Ok(())
+}
+
+pub fn public_inputs(
+    claim_digest: [u8; 32],
+    allowed_control_root: &str,
+    bn254_identity_control_id: &str,
+) -> Result<PublicInputs<5>, ProgramError> {
+    let allowed_control_root: Digest = digest_from_hex(allowed_control_root);
+    let bn254_identity_control_id: Digest = digest_from_hex(bn254_identity_control_id);
+
+    let (a0, a1) =
+        split_digest_bytes(allowed_control_root).map_err(|_| ProgramError::InvalidAccountData)?;
+    let (c0, c1) = split_digest_bytes(Digest::from(claim_digest))
+        .map_err(|_| ProgramError::InvalidAccountData)?;
+
+    let id_bn254_fr = identity_control_id_to_fr(bn254_identity_control_id);
+
+    let inputs = [a0, a1, c0, c1, id_bn254_fr];
+
+    Ok(PublicInputs { inputs })
+}
+
+/// Builds the public inputs for a circuit that commits to `C` claim digests.
+///
+/// The layout extends the standard RISC Zero one: the two halves of the control root,
+/// the two halves of each claim digest in order, and finally the BN254 identity control ID,
+/// for a total of `2 + 2 * C + 1` inputs.
+///
+/// Fails with [`Risc0SolanaError::InvalidPublicInput`] if either hex string is not a 32-byte
+/// digest, or if the identity control ID is not a valid scalar. Digest halves are 128-bit
+/// values and always fit in the scalar field.
+pub fn public_inputs_n_claims<const C: usize>(
+    claim_digests: [[u8; 32]; C],
+    allowed_control_root: &str,
+    bn254_identity_control_id: &str,
+) -> Result<PublicInputsVec, ProgramError> {
+    let allowed_control_root = try_digest_from_hex(allowed_control_root)?;
+    let bn254_identity_control_id = try_digest_from_hex(bn254_identity_control_id)?;
+
+    let (a0, a1) =
+        split_digest_bytes(allowed_control_root).map_err(|_| ProgramError::InvalidAccountData)?;
+
+    let mut inputs = Vec::with_capacity(2 * C + 3);
+    inputs.extend([a0, a1]);
+    for claim_digest in claim_digests {
+        let (c0, c1) = split_digest_bytes(Digest::from(claim_digest))
+            .map_err(|_| ProgramError::InvalidAccountData)?;
+        inputs.extend([c0, c1]);
+    }
+
+    let id_bn254_fr = identity_control_id_to_fr(bn254_identity_control_id);
+    if !is_scalar_valid(&id_bn254_fr) {
+        return Err(Risc0SolanaError::InvalidPublicInput.into());
+    }
+    inputs.push(id_bn254_fr);
+
+    Ok(PublicInputsVec { inputs })
+}
+
+/// Computes the SHA-256 digest of a guest journal.
+pub fn compute_journal_digest(journal: &[u8]) -> [u8; 32] {
+    hashv(&[journal]).to_bytes()
+}
+
+/// Computes a journal digest bound to an application-specific `domain`.
+///
+/// The journal is hashed after the SHA-256 digest of `domain`, so the encoding is unambiguous
+/// for any pair of domain and journal. Guests must commit to the digest computed the same way.
+pub fn compute_journal_digest_with_domain(domain: &[u8], journal: &[u8]) -> [u8; 32] {
+    let domain_tag = hashv(&[domain]).to_bytes();
+    hashv(&[&domain_tag, journal]).to_bytes()
+}
+
+/// Computes the `ReceiptClaim` digest for a guest with the given `image_id` that halted
+/// successfully (exit code 0) with no input and no assumptions, committing a journal
+/// with digest `journal_digest`.
+pub fn compute_claim_digest(image_id: &[u8; 32], journal_digest: &[u8; 32]) -> [u8; 32] {
+    compute_claim_digest_with_assumptions(image_id, journal_digest, &[0u8; 32])
+}
+
+/// Computes the `ReceiptClaim` digest like [`compute_claim_digest`], for a guest whose
+/// receipt carries assumptions.
+///
+/// Guests that call `env::verify` produce composite receipts whose output commits to a
+/// non-zero `assumptions_digest`. Passing `[0u8; 32]` is equivalent to
+/// [`compute_claim_digest`].
+pub fn compute_claim_digest_with_assumptions(
+    image_id: &[u8; 32],
+    journal_digest: &[u8; 32],
+    assumptions_digest: &[u8; 32],
+) -> [u8; 32] {
+    let output_digest = compute_output_digest(journal_digest, assumptions_digest);
+    compute_receipt_claim_digest(
+        &[0u8; 32],
+        image_id,
+        &SYSTEM_STATE_ZERO_DIGEST,
+        &output_digest,
+        0,
+        0,
+    )
+}
+
+/// Converts an image ID given as eight `u32` words, as printed by the RISC Zero build tools,
+/// into the 32-byte form taken by [`compute_claim_digest`].
+///
+/// Each word is little-endian, matching `Digest::from([u32; 8])`.
+pub const fn image_id_to_bytes(words: [u32; 8]) -> [u8; 32] {
+    let mut bytes = [0u8; 32];
+    let mut i = 0;
+    while i < 8 {
+        let word = words[i].to_le_bytes();
+        bytes[4 * i] = word[0];
+        bytes[4 * i + 1] = word[1];
+        bytes[4 * i + 2] = word[2];
+        bytes[4 * i + 3] = word[3];
+        i += 1;
+    }
+    bytes
+}
+
+/// Computes the journal digest and the claim digest for a raw journal in one call.
+///
+/// Returns `(journal_digest, claim_digest)`.
+pub fn compute_digests(image_id: &[u8; 32], journal: &[u8]) -> ([u8; 32], [u8; 32]) {
+    let journal_digest = compute_journal_digest(journal);
+    let claim_digest = compute_claim_digest(image_id, &journal_digest);
+    (journal_digest, claim_digest)
+}
+
+fn compute_output_digest(journal_digest: &[u8; 32], assumptions_digest: &[u8; 32]) -> [u8; 32] {
+    hashv(&[
+        &OUTPUT_TAG,
+        journal_digest,
+        assumptions_digest,
+        &2u16.to_le_bytes(),
+    ])
+    .to_bytes()
+}
+
+fn compute_receipt_claim_digest(
+    input: &[u8; 32],
+    pre: &[u8; 32],
+    post: &[u8; 32],
+    output: &[u8; 32],
+    system_exit: u32,
+    user_exit: u32,
+) -> [u8; 32] {
+    hashv(&[
+        &RECEIPT_CLAIM_TAG,
+        input,
+        pre,
+        post,
+        output,
+        &system_exit.to_le_bytes(),
+        &user_exit.to_le_bytes(),
+        &4u16.to_le_bytes(),
+    ])
+    .to_bytes()
+}
+
+fn digest_from_hex(hex_str: &str) -> Digest {
+    let bytes = hex::decode(hex_str).expect("Invalid hex string");
+    Digest::from_bytes(bytes.try_into().expect("Invalid digest length"))
+}
+
+fn try_digest_from_hex(hex_str: &str) -> Result<Digest, Risc0SolanaError> {
+    let bytes: [u8; 32] = hex::decode(hex_str)
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or(Risc0SolanaError::InvalidPublicInput)?;
+    Ok(Digest::from_bytes(bytes))
+}
+
+fn split_digest_bytes(d: Digest) -> Result<([u8; 32], [u8; 32]), anyhow::Error> {
+    let big_endian: Vec<u8> = d.as_bytes().iter().rev().copied().collect();
+    let middle = big_endian.len() / 2;
+    let (b, a) = big_endian.split_at(middle);
+    Ok((to_fixed_array(a), to_fixed_array(b)))
+}
+
+fn identity_control_id_to_fr(bn254_identity_control_id: Digest) -> [u8; 32] {
+    let mut id_bn554 = bn254_identity_control_id.as_bytes().to_vec();
+    id_bn554.reverse();
+    to_fixed_array(&id_bn554)
+}
+
+fn to_fixed_array(input: &[u8]) -> [u8; 32] {
+    assert!(input.len() <= 32, "Input length must not exceed 32 bytes");
+
+    let mut fixed_array = [0u8; 32];
+    let start_index = 32 - input.len();
+
+    fixed_array[start_index..].copy_from_slice(input);
+    fixed_array
+}
+
+// Checks that every 32-byte big-endian coordinate of a point is below `q`.
+#[cfg(not(target_os = "solana"))]
+fn coordinates_in_field(point: &[u8]) -> bool {
+    point
+        .chunks(32)
+        .all(|coordinate| coordinate.try_into().is_ok_and(is_scalar_valid))
+}
+
+fn is_scalar_valid(scalar: &[u8; 32]) -> bool {
+    for (s_byte, q_byte) in scalar.iter().zip(BASE_FIELD_MODULUS_Q.iter()) {
+        match s_byte.cmp(q_byte) {
+            std::cmp::Ordering::Less => return true,     // scalar < q
             std::cmp::Ordering::Greater => return false, // scalar > q
             std::cmp::Ordering::Equal => continue,       // check next
         }
@@ -203,13 +1209,21 @@ fn is_scalar_valid(scalar: &[u8; 32]) -> bool {
 pub mod client {
 
     use super::*;
+    #[cfg(feature = "receipt")]
+    use risc0_zkvm::{sha::Digestible, InnerReceipt, Receipt};
     use {
         anyhow::{anyhow, Error, Result},
         ark_serialize::{CanonicalDeserialize, CanonicalSerialize, Compress, Validate},
         num_bigint::BigUint,
         serde::{Deserialize, Deserializer, Serialize},
         solana_program::alt_bn128::compression::prelude::convert_endianness,
-        std::{convert::TryInto, fs::File, io::Write},
+        std::{
+            collections::{hash_map::Entry, HashMap},
+            convert::TryInto,
+            fs::File,
+            io::Write,
+            path::Path,
+        },
     };
 
     type G1 = ark_bn254::g1::G1Affine;
@@ -274,12 +1288,17 @@ pub mod client {
         where
             S: serde::Serializer,
         {
-            let strings: Vec<String> = self
-                .inputs
+            serde::Serialize::serialize(&self.to_decimal_strings(), serializer)
+        }
+    }
+
+    impl<const N: usize> PublicInputs<N> {
+        /// Returns each input as a decimal string, as in a snarkjs `public.json`.
+        pub fn to_decimal_strings(&self) -> Vec<String> {
+            self.inputs
                 .iter()
                 .map(|input| BigUint::from_bytes_be(input).to_string())
-                .collect();
-            serde::Serialize::serialize(&strings, serializer)
+                .collect()
         }
     }
 
@@ -307,6 +1326,7 @@ pub mod client {
         }
     }
 
+    /// Parses decimal strings, as emitted by snarkjs, into big-endian scalars.
     impl<const N: usize> TryFrom<Vec<String>> for PublicInputs<N> {
         type Error = Error;
 
@@ -348,6 +1368,57 @@ pub mod client {
                 vk_ic: self.vk_ic.iter().map(export_g1).collect(),
             })
         }
+
+        /// Exports the key as JSON using gnark's `VerifyingKey` field names and point layout.
+        ///
+        /// Coordinates are decimal strings and G2 coordinates are split into `A0`/`A1`, as in
+        /// gnark-crypto's `E2`. Only the fields this crate knows about (`G1.Alpha`, `G1.K`,
+        /// `G2.Beta`, `G2.Gamma`, `G2.Delta`) are emitted.
+        pub fn to_gnark_json(&self) -> String {
+            serde_json::json!({
+                "G1": {
+                    "Alpha": gnark_g1(&self.vk_alpha_g1),
+                    "K": self.vk_ic.iter().map(gnark_g1).collect::<Vec<_>>(),
+                },
+                "G2": {
+                    "Beta": gnark_g2(&self.vk_beta_g2),
+                    "Gamma": gnark_g2(&self.vk_gamma_g2),
+                    "Delta": gnark_g2(&self.vk_delta_g2),
+                },
+            })
+            .to_string()
+        }
+
+        /// Decodes each `vk_ic` entry into an arkworks G1 point, e.g. to inspect or plot the key.
+        ///
+        /// Fails if any entry is not a valid point on the curve.
+        pub fn ic_points(&self) -> Result<Vec<G1>> {
+            self.vk_ic
+                .iter()
+                .enumerate()
+                .map(|(i, bytes)| {
+                    G1::deserialize_with_mode(
+                        convert_endianness::<32, 64>(bytes).as_slice(),
+                        Compress::No,
+                        Validate::Yes,
+                    )
+                    .map_err(|e| anyhow!("Invalid IC point {}: {:?}", i, e))
+                })
+                .collect()
+        }
+    }
+
+    fn gnark_g1(bytes: &[u8; G1_LEN]) -> serde_json::Value {
+        let point = export_g1(bytes);
+        serde_json::json!({ "X": point[0], "Y": point[1] })
+    }
+
+    fn gnark_g2(bytes: &[u8; G2_LEN]) -> serde_json::Value {
+        let point = export_g2(bytes);
+        serde_json::json!({
+            "X": { "A0": point[0][0], "A1": point[0][1] },
+            "Y": { "A0": point[1][0], "A1": point[1][1] },
+        })
     }
 
     impl<'de> Deserialize<'de> for Proof {
@@ -496,6 +1567,276 @@ pub mod client {
         ]
     }
 
+    /// Memoizes the `vk_ic[i + 1] * input_i` products used to prepare public inputs.
+    ///
+    /// Bulk verification services often see the same input values (e.g. the control root
+    /// halves) over and over; the cache skips the repeated scalar multiplications. Entries are
+    /// keyed by the IC point itself, so one cache can safely be shared across keys.
+    #[derive(Debug, Default)]
+    pub struct PreparedInputCache {
+        products: HashMap<([u8; G1_LEN], [u8; 32]), [u8; G1_LEN]>,
+    }
+
+    impl PreparedInputCache {
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        /// Number of cached products.
+        pub fn len(&self) -> usize {
+            self.products.len()
+        }
+
+        pub fn is_empty(&self) -> bool {
+            self.products.is_empty()
+        }
+
+        /// Computes `vk_ic[0] + sum(vk_ic[i + 1] * input_i)`, reusing cached products.
+        pub fn prepare<const N: usize>(
+            &mut self,
+            public: &PublicInputs<N>,
+            vk: &VerificationKey,
+        ) -> Result<[u8; G1_LEN]> {
+            if vk.vk_ic.len() != N + 1 {
+                return Err(anyhow!(
+                    "Invalid vk_ic length: expected {}, got {}",
+                    N + 1,
+                    vk.vk_ic.len()
+                ));
+            }
+
+            let mut prepared = vk.vk_ic[0];
+            for (i, input) in public.inputs.iter().enumerate() {
+                if !is_scalar_valid(input) {
+                    return Err(anyhow!("Public input {} is not a valid scalar", i));
+                }
+                let product = match self.products.entry((vk.vk_ic[i + 1], *input)) {
+                    Entry::Occupied(entry) => *entry.get(),
+                    Entry::Vacant(entry) => {
+                        let product =
+                            alt_bn128_multiplication(&[&vk.vk_ic[i + 1][..], &input[..]].concat())
+                                .map_err(|e| anyhow!("Multiplication failed: {:?}", e))?;
+                        *entry.insert(
+                            product
+                                .try_into()
+                                .map_err(|_| anyhow!("Multiplication returned an invalid point"))?,
+                        )
+                    }
+                };
+                prepared = alt_bn128_addition(&[&product[..], &prepared[..]].concat())
+                    .map_err(|e| anyhow!("Addition failed: {:?}", e))?
+                    .try_into()
+                    .map_err(|_| anyhow!("Addition returned an invalid point"))?;
+            }
+
+            Ok(prepared)
+        }
+    }
+
+    /// Verifies a proof like [`verify_proof`], preparing the public inputs through `cache`.
+    pub fn verify_with_cache<const N: usize>(
+        cache: &mut PreparedInputCache,
+        proof: &Proof,
+        public: &PublicInputs<N>,
+        vk: &VerificationKey,
+    ) -> Result<()> {
+        reject_infinity_pi_b(proof).map_err(|e| anyhow!("Proof verification failed: {:?}", e))?;
+        let prepared = cache.prepare(public, vk)?;
+        verify_prepared(proof, &prepared, vk)
+            .map_err(|e| anyhow!("Proof verification failed: {:?}", e))
+    }
+
+    /// Outcome of [`diagnose_mismatch`].
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum MismatchHint {
+        /// The proof verifies against the key.
+        Verified,
+        /// The key is for a circuit with a different number of public inputs.
+        InputCountMismatch { expected: usize, actual: usize },
+        /// The inputs fit the key but the pairing check fails, most likely because the proof
+        /// was generated for a different verification key.
+        LikelyWrongKey,
+        /// Verification failed before the pairing check, e.g. on an out-of-range input.
+        Malformed(String),
+    }
+
+    /// Explains why a proof does not verify against `vk`.
+    ///
+    /// Distinguishes public inputs that cannot belong to this key (IC length mismatch) from a
+    /// proof that is well-formed for the key but fails the pairing check.
+    pub fn diagnose_mismatch<const N: usize>(
+        proof: &Proof,
+        public: &PublicInputs<N>,
+        vk: &VerificationKey,
+    ) -> MismatchHint {
+        if vk.vk_ic.len() != N + 1 {
+            return MismatchHint::InputCountMismatch {
+                expected: vk.vk_ic.len().saturating_sub(1),
+                actual: N,
+            };
+        }
+        match verify_proof(proof, public, vk) {
+            Ok(()) => MismatchHint::Verified,
+            Err(ProgramError::Custom(code)) if code == Risc0SolanaError::PairingMismatch as u32 => {
+                MismatchHint::LikelyWrongKey
+            }
+            Err(e) => MismatchHint::Malformed(format!("{:?}", e)),
+        }
+    }
+
+    /// Returns true if the proof only fails because `pi_a` was not negated.
+    ///
+    /// That is, verification fails as-is but succeeds once `pi_a` is negated, which is what
+    /// happens when a proof is passed to [`verify_proof`] exactly as the prover emitted it.
+    pub fn needs_negation<const N: usize>(
+        proof: &Proof,
+        public: &PublicInputs<N>,
+        vk: &VerificationKey,
+    ) -> bool {
+        if verify_proof(proof, public, vk).is_ok() {
+            return false;
+        }
+        let Ok(pi_a) = negate_g1(&proof.pi_a) else {
+            return false;
+        };
+        let negated = Proof {
+            pi_a,
+            ..proof.clone()
+        };
+        verify_proof(&negated, public, vk).is_ok()
+    }
+
+    /// Verifies a proof and estimates the compute units the same check costs on-chain.
+    ///
+    /// Returns whether the proof verified together with [`estimated_compute_units`] for `N`
+    /// inputs. Fails only if `vk` does not have exactly `N` inputs.
+    pub fn verify_and_estimate<const N: usize>(
+        proof: &Proof,
+        public: &PublicInputs<N>,
+        vk: &VerificationKey,
+    ) -> Result<(bool, u64)> {
+        if vk.vk_ic.len() != N + 1 {
+            return Err(anyhow!(
+                "Verification key expects {} public inputs, got {}",
+                vk.vk_ic.len().saturating_sub(1),
+                N
+            ));
+        }
+        let verified = verify_proof(proof, public, vk).is_ok();
+        Ok((verified, estimated_compute_units(N)))
+    }
+
+    /// Verifies a proof read from snarkjs-style JSON files.
+    ///
+    /// `proof_path` holds the proof as produced by the prover (i.e. `pi_a` is **not** negated),
+    /// `public_path` holds the five RISC Zero public inputs as decimal strings, and `vk_path`
+    /// holds the verification key.
+    pub fn verify_from_files(vk_path: &str, proof_path: &str, public_path: &str) -> Result<()> {
+        let vk: VerificationKey = serde_json::from_reader(File::open(vk_path)?)
+            .map_err(|e| anyhow!("Failed to parse verification key {}: {}", vk_path, e))?;
+        let mut proof: Proof = serde_json::from_reader(File::open(proof_path)?)
+            .map_err(|e| anyhow!("Failed to parse proof {}: {}", proof_path, e))?;
+        let public: PublicInputs<5> = serde_json::from_reader(File::open(public_path)?)
+            .map_err(|e| anyhow!("Failed to parse public inputs {}: {}", public_path, e))?;
+
+        #[cfg(debug_assertions)]
+        if public.looks_malformed() {
+            eprintln!("Warning: public inputs in {} look malformed", public_path);
+        }
+
+        proof.pi_a = negate_g1(&proof.pi_a)?;
+
+        verify_proof(&proof, &public, &vk)
+            .map_err(|e| anyhow!("Proof verification failed: {:?}", e))
+    }
+
+    /// Checks that a snarkjs verification key matches a circuit with `expected_public` inputs.
+    ///
+    /// Both `nPublic` and the number of `IC` points are checked, since a key can be edited
+    /// so that they disagree.
+    pub fn validate_vk_arity(vk_json: &str, expected_public: u32) -> Result<()> {
+        let vk: VerificationKey = serde_json::from_str(vk_json)
+            .map_err(|e| anyhow!("Failed to parse verification key: {}", e))?;
+
+        if vk.nr_pubinputs != expected_public {
+            return Err(anyhow!(
+                "Verification key expects {} public inputs, circuit has {}",
+                vk.nr_pubinputs,
+                expected_public
+            ));
+        }
+        if vk.vk_ic.len() != expected_public as usize + 1 {
+            return Err(anyhow!(
+                "Verification key has {} IC points, expected {}",
+                vk.vk_ic.len(),
+                expected_public as usize + 1
+            ));
+        }
+        Ok(())
+    }
+
+    /// Writes everything needed to reproduce a verification to `dir`.
+    ///
+    /// Creates `proof.json`, `public.json`, `vk.json` and a `report.txt` describing the outcome
+    /// of [`verify_proof`]. The proof is written as given, so `pi_a` stays negated.
+    pub fn dump_failure_bundle<const N: usize>(
+        dir: &str,
+        proof: &Proof,
+        public: &PublicInputs<N>,
+        vk: &VerificationKey,
+    ) -> Result<()> {
+        let dir = Path::new(dir);
+        std::fs::create_dir_all(dir)?;
+
+        std::fs::write(dir.join("proof.json"), serde_json::to_string_pretty(proof)?)?;
+        std::fs::write(
+            dir.join("public.json"),
+            serde_json::to_string_pretty(public)?,
+        )?;
+        std::fs::write(dir.join("vk.json"), serde_json::to_string_pretty(vk)?)?;
+
+        let outcome = match verify_proof(proof, public, vk) {
+            Ok(()) => "verified".to_string(),
+            Err(e) => format!("failed: {:?}", e),
+        };
+        let report = format!(
+            "result: {}\npublic inputs: {}\nvk nr_pubinputs: {}\nvk ic points: {}\n",
+            outcome,
+            N,
+            vk.nr_pubinputs,
+            vk.vk_ic.len()
+        );
+        std::fs::write(dir.join("report.txt"), report)?;
+
+        Ok(())
+    }
+
+    /// Returns the exact SHA-256 preimage hashed by `compute_receipt_claim_digest`.
+    ///
+    /// The layout is `tag || input || pre || post || output || system_exit || user_exit ||
+    /// down_len`, with the exit codes as little-endian `u32` and the number of digests
+    /// (always 4) as little-endian `u16`. Handy for diffing against other implementations.
+    pub fn receipt_claim_preimage(
+        input: &[u8; 32],
+        pre: &[u8; 32],
+        post: &[u8; 32],
+        output: &[u8; 32],
+        system_exit: u32,
+        user_exit: u32,
+    ) -> Vec<u8> {
+        [
+            RECEIPT_CLAIM_TAG.as_slice(),
+            input,
+            pre,
+            post,
+            output,
+            &system_exit.to_le_bytes(),
+            &user_exit.to_le_bytes(),
+            &4u16.to_le_bytes(),
+        ]
+        .concat()
+    }
+
     pub fn write_to_file(filename: &str, proof: &Proof) {
         let mut file = File::create(filename).expect("Failed to create file");
         file.write_all(&proof.pi_a)
@@ -527,56 +1868,440 @@ pub mod client {
         convert_endianness::<64, 64>(&compressed)
     }
 
-    pub fn negate_g1(point: &[u8; 64]) -> Result<[u8; 64], Error> {
-        let x = &point[..32];
-        let y = &point[32..];
+    /// Builds the 160-byte payload of the hello example's `VerifyProof` instruction.
+    ///
+    /// The layout is `claim_digest (32) | compressed_a (32) | compressed_b (64) | compressed_c (32)`,
+    /// with `pi_a` already negated. The instruction tag is not included. The control root and
+    /// identity control ID are not part of the payload; the program supplies its own.
+    #[cfg(feature = "receipt")]
+    pub fn build_verify_instruction_data(receipt: &Receipt) -> Result<Vec<u8>> {
+        let groth16 = receipt
+            .inner
+            .groth16()
+            .map_err(|e| anyhow!("Receipt is not a Groth16 receipt: {}", e))?;
+        let (proof, _) = proof_variants(&groth16.seal)?;
+        let claim_digest: [u8; 32] = groth16.claim.digest().as_bytes().try_into()?;
+
+        Ok([
+            claim_digest.as_slice(),
+            &compress_g1_be(&proof.pi_a),
+            &compress_g2_be(&proof.pi_b),
+            &compress_g1_be(&proof.pi_c),
+        ]
+        .concat())
+    }
 
-        let mut y_big = BigUint::from_bytes_be(y);
-        let field_modulus = BigUint::from_bytes_be(&BASE_FIELD_MODULUS_Q);
+    /// Extracts the flag bits of a compressed G1 (32 bytes) or G2 (64 bytes) point.
+    ///
+    /// Returns `(sign, infinity)` as encoded by [`compress_g1_be`] and [`compress_g2_be`]: the
+    /// top bit of the first byte is set when the omitted `y` is the larger of its two roots,
+    /// the next bit marks the point at infinity. Nothing is decompressed or validated.
+    pub fn compressed_point_info(bytes: &[u8]) -> (bool, bool) {
+        let flags = bytes.first().copied().unwrap_or(0);
+        (flags & 0x80 != 0, flags & 0x40 != 0)
+    }
 
-        // Negate the y-coordinate to get -g1.
-        y_big = field_modulus - y_big;
+    /// A term of the Groth16 pairing product checked by [`verify_proof`].
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum PairingTerm {
+        /// `e(-pi_a, pi_b)`
+        ProofAB,
+        /// `e(prepared_inputs, vk_gamma_g2)`
+        PreparedInputsGamma,
+        /// `e(pi_c, vk_delta_g2)`
+        ProofCDelta,
+        /// `e(vk_alpha_g1, vk_beta_g2)`
+        AlphaBeta,
+    }
 
-        // Reconstruct the point with the negated y-coordinate
-        let mut result = [0u8; 64];
-        result[..32].copy_from_slice(x);
-        let y_bytes = y_big.to_bytes_be();
-        result[64 - y_bytes.len()..].copy_from_slice(&y_bytes);
+    /// Points at the pairing term responsible for a failing proof, for debugging mismatches
+    /// between implementations. Computed off-chain with arkworks.
+    ///
+    /// A term is reported if one of its points fails to decode, or if inverting just that term
+    /// would make the product the identity, which is what sign and negation mix-ups produce.
+    /// Returns `None` if the proof verifies or no single term can be blamed.
+    pub fn pairing_failure_witness<const N: usize>(
+        proof: &Proof,
+        public: &PublicInputs<N>,
+        vk: &VerificationKey,
+    ) -> Option<PairingTerm> {
+        use ark_ec::pairing::Pairing;
+        use num_traits::Zero;
+
+        let prepared = prepare_public_inputs(public, vk).ok()?;
+        let g1 = |bytes: &[u8; G1_LEN]| {
+            let le = convert_endianness::<32, 64>(bytes);
+            G1::deserialize_with_mode(le.as_slice(), Compress::No, Validate::Yes).ok()
+        };
+        let g2 = |bytes: &[u8; G2_LEN]| {
+            let le = convert_endianness::<64, 128>(bytes);
+            G2::deserialize_with_mode(le.as_slice(), Compress::No, Validate::Yes).ok()
+        };
+        let decode = |a: Option<G1>, b: Option<G2>, term| a.zip(b).ok_or(term);
+
+        let pairs = [
+            decode(g1(&proof.pi_a), g2(&proof.pi_b), PairingTerm::ProofAB),
+            decode(
+                g1(&prepared),
+                g2(&vk.vk_gamma_g2),
+                PairingTerm::PreparedInputsGamma,
+            ),
+            decode(
+                g1(&proof.pi_c),
+                g2(&vk.vk_delta_g2),
+                PairingTerm::ProofCDelta,
+            ),
+            decode(
+                g1(&vk.vk_alpha_g1),
+                g2(&vk.vk_beta_g2),
+                PairingTerm::AlphaBeta,
+            ),
+        ]
+        .into_iter()
+        .collect::<std::result::Result<Vec<_>, _>>();
+        let pairs = match pairs {
+            Ok(pairs) => pairs,
+            Err(term) => return Some(term),
+        };
 
-        Ok(result)
+        let terms: Vec<_> = pairs
+            .iter()
+            .map(|(a, b)| ark_bn254::Bn254::pairing(a, b))
+            .collect();
+        let product = terms
+            .iter()
+            .copied()
+            .sum::<ark_ec::pairing::PairingOutput<_>>();
+        if product.is_zero() {
+            return None;
+        }
+
+        [
+            PairingTerm::ProofAB,
+            PairingTerm::PreparedInputsGamma,
+            PairingTerm::ProofCDelta,
+            PairingTerm::AlphaBeta,
+        ]
+        .into_iter()
+        .zip(terms)
+        .find(|(_, term)| product == *term + term)
+        .map(|(name, _)| name)
     }
-}
 
-#[cfg(test)]
-mod test_lib {
-    use super::client::*;
-    use super::*;
-    use risc0_zkvm::sha::Digestible;
-    use risc0_zkvm::Receipt;
-    use std::fs::File;
-    use std::io::Write;
+    /// Compares two proofs by their decoded curve points rather than their raw bytes.
+    ///
+    /// Each element is decoded both as big-endian (this crate's layout) and as little-endian
+    /// (arkworks' native layout), so a proof and its endianness-flipped twin compare equal.
+    /// Useful to diagnose byte-order mistakes when two proofs unexpectedly differ. Byte-identical
+    /// proofs always compare equal, even if their points fail to decode.
+    pub fn proofs_equal_mod_endianness(a: &Proof, b: &Proof) -> bool {
+        if a == b {
+            return true;
+        }
+        points_match(&g1_candidates(&a.pi_a), &g1_candidates(&b.pi_a))
+            && points_match(&g2_candidates(&a.pi_b), &g2_candidates(&b.pi_b))
+            && points_match(&g1_candidates(&a.pi_c), &g1_candidates(&b.pi_c))
+    }
 
-    // From: https://github.com/risc0/risc0/blob/v1.1.1/risc0/circuit/recursion/src/control_id.rs#L47
-    const ALLOWED_CONTROL_ROOT: &str =
-        "8b6dcf11d463ac455361b41fb3ed053febb817491bdea00fdb340e45013b852e";
-    const BN254_IDENTITY_CONTROL_ID: &str =
-        "4e160df1e119ac0e3d658755a9edf38c8feb307b34bc10b57f4538dbe122a005";
+    fn g1_candidates(bytes: &[u8; G1_LEN]) -> Vec<G1> {
+        [convert_endianness::<32, 64>(bytes), *bytes]
+            .iter()
+            .filter_map(|le| {
+                G1::deserialize_with_mode(le.as_slice(), Compress::No, Validate::Yes).ok()
+            })
+            .collect()
+    }
 
-    // Reference base field modulus for BN254
-    // https://docs.rs/ark-bn254/latest/ark_bn254/
-    const REF_BASE_FIELD_MODULUS: &str =
-        "21888242871839275222246405745257275088696311157297823662689037894645226208583";
+    fn g2_candidates(bytes: &[u8; G2_LEN]) -> Vec<G2> {
+        [convert_endianness::<64, 128>(bytes), *bytes]
+            .iter()
+            .filter_map(|le| {
+                G2::deserialize_with_mode(le.as_slice(), Compress::No, Validate::Yes).ok()
+            })
+            .collect()
+    }
 
-    fn load_receipt_and_extract_data() -> (Receipt, Proof, PublicInputs<5>) {
-        let receipt_json_str = include_bytes!("../test/data/receipt.json");
-        let receipt: Receipt = serde_json::from_slice(receipt_json_str).unwrap();
+    fn points_match<P: PartialEq>(a: &[P], b: &[P]) -> bool {
+        a.iter().any(|p| b.contains(p))
+    }
 
-        let claim_digest = receipt
+    /// Builds the RISC Zero public inputs for a Groth16 receipt from its claim digest.
+    #[cfg(feature = "receipt")]
+    pub fn public_inputs_from_receipt(
+        receipt: &Receipt,
+        allowed_control_root: &str,
+        bn254_identity_control_id: &str,
+    ) -> Result<PublicInputs<5>> {
+        let groth16 = receipt
             .inner
             .groth16()
-            .unwrap()
-            .claim
-            .digest()
+            .map_err(|e| anyhow!("Receipt is not a Groth16 receipt: {}", e))?;
+        let claim_digest: [u8; 32] = groth16.claim.digest().as_bytes().try_into()?;
+
+        public_inputs(
+            claim_digest,
+            allowed_control_root,
+            bn254_identity_control_id,
+        )
+        .map_err(|e| anyhow!("Failed to build public inputs: {:?}", e))
+    }
+
+    /// Returns true if both public input sets attest to the same claim.
+    ///
+    /// Only the claim digest scalars (`inputs[2]` and `inputs[3]`) are compared, so proofs of
+    /// one claim produced under different control roots are still considered the same.
+    pub fn same_claim(a: &PublicInputs<5>, b: &PublicInputs<5>) -> bool {
+        a.inputs[2..4] == b.inputs[2..4]
+    }
+
+    /// Verifies a proof whose points were already parsed with arkworks.
+    ///
+    /// The points are serialized into this crate's big-endian layout and passed to
+    /// [`verify_proof`], so as there, `a` must already be negated.
+    pub fn verify_ark<const N: usize>(
+        proof: (G1, G2, G1),
+        public: &PublicInputs<N>,
+        vk: &VerificationKey,
+    ) -> Result<()> {
+        let (a, b, c) = proof;
+        let proof = Proof {
+            pi_a: ark_g1_to_bytes(&a)?,
+            pi_b: ark_g2_to_bytes(&b)?,
+            pi_c: ark_g1_to_bytes(&c)?,
+        };
+        verify_proof(&proof, public, vk).map_err(|e| anyhow!("Proof verification failed: {:?}", e))
+    }
+
+    fn ark_g1_to_bytes(point: &G1) -> Result<[u8; G1_LEN]> {
+        let mut le = [0u8; G1_LEN];
+        G1::serialize_with_mode(point, &mut le[..], Compress::No)
+            .map_err(|e| anyhow!("Failed to serialize G1 point: {:?}", e))?;
+        Ok(convert_endianness::<32, 64>(&le))
+    }
+
+    fn ark_g2_to_bytes(point: &G2) -> Result<[u8; G2_LEN]> {
+        let mut le = [0u8; G2_LEN];
+        G2::serialize_with_mode(point, &mut le[..], Compress::No)
+            .map_err(|e| anyhow!("Failed to serialize G2 point: {:?}", e))?;
+        Ok(convert_endianness::<64, 128>(&le))
+    }
+
+    /// Runs every check needed before submitting a receipt on-chain.
+    ///
+    /// Verifies `receipt` locally against `image_id`, then extracts and negates its Groth16
+    /// proof, builds the public inputs from the claim digest and verifies them with
+    /// [`verify_proof`] against `vk`, exactly as the on-chain program would. Dev-mode receipts
+    /// are rejected up front, see [`is_dev_mode_receipt`].
+    #[cfg(feature = "receipt")]
+    pub fn verify_receipt_full(
+        receipt: &Receipt,
+        image_id: impl Into<Digest>,
+        vk: &VerificationKey,
+        allowed_control_root: &str,
+        bn254_identity_control_id: &str,
+    ) -> Result<()> {
+        if is_dev_mode_receipt(receipt) {
+            return Err(anyhow!("dev-mode receipt cannot be verified"));
+        }
+        receipt
+            .verify(image_id)
+            .map_err(|e| anyhow!("Receipt verification failed: {}", e))?;
+
+        verify_receipt(receipt, vk, allowed_control_root, bn254_identity_control_id)
+    }
+
+    /// Image ID of the guest that produced the receipt in `test/data/receipt.json`.
+    ///
+    /// Convert it with [`image_id_to_bytes`] or `Digest::from`.
+    pub const EXAMPLE_IMAGE_ID: [u32; 8] = [
+        2527524877, 681141535, 1616504839, 1015548581, 3681865348, 2252139915, 2462260770,
+        3655813548,
+    ];
+
+    /// Returns true if `receipt` was produced in RISC Zero dev mode.
+    ///
+    /// Dev-mode receipts carry no proof, so they can never pass on-chain verification.
+    #[cfg(feature = "receipt")]
+    pub fn is_dev_mode_receipt(receipt: &Receipt) -> bool {
+        matches!(receipt.inner, InnerReceipt::Fake(_))
+    }
+
+    /// Aggregate result of [`verify_receipts`].
+    #[cfg(feature = "receipt")]
+    #[derive(Debug, Clone, PartialEq, Eq, Default)]
+    pub struct BatchReport {
+        pub passed: usize,
+        /// Index and error message of every receipt that failed.
+        pub failed: Vec<(usize, String)>,
+    }
+
+    /// Runs [`verify_receipt_full`] on every receipt and summarizes the outcome.
+    #[cfg(feature = "receipt")]
+    pub fn verify_receipts(
+        receipts: &[Receipt],
+        image_id: impl Into<Digest>,
+        vk: &VerificationKey,
+        allowed_control_root: &str,
+        bn254_identity_control_id: &str,
+    ) -> BatchReport {
+        let image_id = image_id.into();
+        let mut report = BatchReport::default();
+        for (i, receipt) in receipts.iter().enumerate() {
+            match verify_receipt_full(
+                receipt,
+                image_id,
+                vk,
+                allowed_control_root,
+                bn254_identity_control_id,
+            ) {
+                Ok(()) => report.passed += 1,
+                Err(e) => report.failed.push((i, e.to_string())),
+            }
+        }
+        report
+    }
+
+    /// Verifies a receipt's Groth16 proof after checking the `risc0-zkvm` version.
+    ///
+    /// Fails if [`risc0_zkvm::VERSION`] differs from `expected_version`, since a different
+    /// release may use a control root other than `allowed_control_root`.
+    #[cfg(feature = "receipt")]
+    pub fn verify_with_version_check(
+        receipt: &Receipt,
+        expected_version: &str,
+        vk: &VerificationKey,
+        allowed_control_root: &str,
+        bn254_identity_control_id: &str,
+    ) -> Result<()> {
+        if risc0_zkvm::VERSION != expected_version {
+            return Err(anyhow!(
+                "risc0-zkvm version mismatch: expected {}, found {}",
+                expected_version,
+                risc0_zkvm::VERSION
+            ));
+        }
+
+        verify_receipt(receipt, vk, allowed_control_root, bn254_identity_control_id)
+    }
+
+    /// Verifies the Groth16 proof of a receipt as the on-chain program would.
+    ///
+    /// Extracts the seal, negates `pi_a`, builds the public inputs from the receipt's claim
+    /// digest and runs [`verify_proof`]. Only the proof is checked: use
+    /// [`verify_receipt_full`] to also verify the receipt against an image ID and its journal.
+    #[cfg(feature = "receipt")]
+    pub fn verify_receipt(
+        receipt: &Receipt,
+        vk: &VerificationKey,
+        allowed_control_root: &str,
+        bn254_identity_control_id: &str,
+    ) -> Result<()> {
+        let seal = &receipt
+            .inner
+            .groth16()
+            .map_err(|e| anyhow!("Receipt is not a Groth16 receipt: {}", e))?
+            .seal;
+        let (proof, _) = proof_variants(seal)?;
+        let public =
+            public_inputs_from_receipt(receipt, allowed_control_root, bn254_identity_control_id)?;
+
+        verify_proof(&proof, &public, vk).map_err(|e| anyhow!("Proof verification failed: {:?}", e))
+    }
+
+    /// Builds a proof from a 256-byte Groth16 seal in both conventions.
+    ///
+    /// Returns `(negated, raw)`: the first has `pi_a` negated as expected by [`verify_proof`],
+    /// the second is the proof exactly as emitted by the prover. Debugging tools can try both.
+    pub fn proof_variants(seal: &[u8]) -> Result<(Proof, Proof)> {
+        if seal.len() != 256 {
+            return Err(anyhow!(
+                "Invalid seal length: expected 256 bytes, got {}",
+                seal.len()
+            ));
+        }
+
+        let raw = Proof {
+            pi_a: seal[0..64].try_into()?,
+            pi_b: seal[64..192].try_into()?,
+            pi_c: seal[192..256].try_into()?,
+        };
+        let mut negated = raw.clone();
+        negated.pi_a = negate_g1(&raw.pi_a)?;
+
+        Ok((negated, raw))
+    }
+
+    /// Negates a big-endian G1 point by replacing `y` with `q - y`.
+    ///
+    /// Fails unless both coordinates are below `q` and the point satisfies `y^2 = x^3 + 3`, so
+    /// byte-order mistakes surface here rather than as an opaque pairing failure. The point at
+    /// infinity, encoded as all zeros, is its own negation.
+    pub fn negate_g1(point: &[u8; 64]) -> Result<[u8; 64], Error> {
+        if *point == [0u8; 64] {
+            return Ok(*point);
+        }
+
+        let x = &point[..32];
+        let y = &point[32..];
+
+        let field_modulus = BigUint::from_bytes_be(&BASE_FIELD_MODULUS_Q);
+        let x_big = BigUint::from_bytes_be(x);
+        let mut y_big = BigUint::from_bytes_be(y);
+
+        if x_big >= field_modulus || y_big >= field_modulus {
+            return Err(anyhow!(
+                "G1 point coordinate is not below the field modulus"
+            ));
+        }
+        if y_big.modpow(&BigUint::from(2u8), &field_modulus)
+            != (x_big.modpow(&BigUint::from(3u8), &field_modulus) + 3u8) % &field_modulus
+        {
+            return Err(anyhow!("G1 point is not on the curve"));
+        }
+
+        // Negate the y-coordinate to get -g1.
+        y_big = field_modulus - y_big;
+
+        // Reconstruct the point with the negated y-coordinate
+        let mut result = [0u8; 64];
+        result[..32].copy_from_slice(x);
+        let y_bytes = y_big.to_bytes_be();
+        result[64 - y_bytes.len()..].copy_from_slice(&y_bytes);
+
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod test_lib {
+    use super::client::*;
+    use super::*;
+    use risc0_zkvm::sha::Digestible;
+    use risc0_zkvm::Receipt;
+    use std::fs::File;
+    use std::io::Write;
+
+    // From: https://github.com/risc0/risc0/blob/v1.1.1/risc0/circuit/recursion/src/control_id.rs#L47
+    const ALLOWED_CONTROL_ROOT: &str =
+        "8b6dcf11d463ac455361b41fb3ed053febb817491bdea00fdb340e45013b852e";
+    const BN254_IDENTITY_CONTROL_ID: &str =
+        "4e160df1e119ac0e3d658755a9edf38c8feb307b34bc10b57f4538dbe122a005";
+
+    // Reference base field modulus for BN254
+    // https://docs.rs/ark-bn254/latest/ark_bn254/
+    const REF_BASE_FIELD_MODULUS: &str =
+        "21888242871839275222246405745257275088696311157297823662689037894645226208583";
+
+    fn load_receipt_and_extract_data() -> (Receipt, Proof, PublicInputs<5>) {
+        let receipt_json_str = include_bytes!("../test/data/receipt.json");
+        let receipt: Receipt = serde_json::from_slice(receipt_json_str).unwrap();
+
+        let claim_digest = receipt
+            .inner
+            .groth16()
+            .unwrap()
+            .claim
+            .digest()
             .try_into()
             .unwrap();
         let public_inputs = public_inputs(
@@ -594,127 +2319,1193 @@ mod test_lib {
         };
         proof.pi_a = negate_g1(&proof.pi_a).unwrap();
 
-        (receipt, proof, public_inputs)
+        (receipt, proof, public_inputs)
+    }
+
+    fn load_verification_key() -> VerificationKey<'static> {
+        let vk_json_str = include_str!("../test/data/r0_test_vk.json");
+        serde_json::from_str(vk_json_str).unwrap()
+    }
+
+    #[test]
+    fn test_error_codes_are_stable() {
+        assert_eq!(Risc0SolanaError::G1_COMPRESSION_ERROR_CODE, 0);
+        assert_eq!(Risc0SolanaError::G2_COMPRESSION_ERROR_CODE, 1);
+        assert_eq!(Risc0SolanaError::VERIFICATION_ERROR_CODE, 2);
+        assert_eq!(Risc0SolanaError::INVALID_PUBLIC_INPUT_CODE, 3);
+        assert_eq!(Risc0SolanaError::ARITHMETIC_ERROR_CODE, 4);
+        assert_eq!(Risc0SolanaError::PAIRING_ERROR_CODE, 5);
+        assert_eq!(Risc0SolanaError::UNEXPECTED_VERIFICATION_KEY_CODE, 6);
+        assert_eq!(Risc0SolanaError::OUTSIDE_VALIDITY_WINDOW_CODE, 7);
+        assert_eq!(Risc0SolanaError::INVALID_VERIFICATION_KEY_CODE, 8);
+        assert_eq!(Risc0SolanaError::PAIRING_MISMATCH_CODE, 9);
+        assert_eq!(Risc0SolanaError::G2_SUBGROUP_ERROR_CODE, 10);
+
+        assert_eq!(
+            ProgramError::from(Risc0SolanaError::VerificationError),
+            ProgramError::Custom(Risc0SolanaError::VERIFICATION_ERROR_CODE)
+        );
+    }
+
+    #[test]
+    fn test_convert_g1_invalid_z() {
+        let values = vec![
+            "1".to_string(), // x
+            "2".to_string(), // y
+            "0".to_string(), // z (invalid)
+        ];
+
+        let result = convert_g1(&values);
+
+        assert!(
+            result.is_err(),
+            "Expected error due to invalid Z coordinate"
+        );
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Invalid G1 point: Z coordinate is not 1 (found 0)"
+        );
+    }
+
+    #[test]
+    fn test_convert_g2_invalid_z() {
+        let values = vec![
+            vec!["1".to_string(), "2".to_string()], // x
+            vec!["3".to_string(), "4".to_string()], // y
+            vec!["0".to_string(), "0".to_string()], // z (invalid)
+        ];
+
+        let result = convert_g2(&values);
+
+        assert!(
+            result.is_err(),
+            "Expected error due to invalid Z coordinate"
+        );
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Invalid G2 point: Z coordinate is not [1, 0] (found [0, 0])"
+        );
+    }
+
+    #[test]
+    fn test_import() {
+        let vk = load_verification_key();
+        println!("Verification Key: {:?}", vk);
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let vk = load_verification_key();
+
+        let exported_json = serde_json::to_string(&vk).unwrap();
+        let reimported_vk: VerificationKey = serde_json::from_str(&exported_json).unwrap();
+
+        assert_eq!(vk, reimported_vk, "Roundtrip serialization failed");
+    }
+
+    #[test]
+    fn test_to_gnark_json() {
+        let vk = load_verification_key();
+        let json: serde_json::Value = serde_json::from_str(&vk.to_gnark_json()).unwrap();
+
+        assert!(json["G1"]["Alpha"]["X"].is_string());
+        assert!(json["G1"]["Alpha"]["Y"].is_string());
+        assert_eq!(json["G1"]["K"].as_array().unwrap().len(), vk.vk_ic.len());
+        for key in ["Beta", "Gamma", "Delta"] {
+            for coord in ["X", "Y"] {
+                assert!(json["G2"][key][coord]["A0"].is_string(), "{key}.{coord}.A0");
+                assert!(json["G2"][key][coord]["A1"].is_string(), "{key}.{coord}.A1");
+            }
+        }
+
+        let vk_json: serde_json::Value = serde_json::to_value(&vk).unwrap();
+        assert_eq!(json["G1"]["Alpha"]["X"], vk_json["vk_alpha_1"][0]);
+        assert_eq!(json["G2"]["Gamma"]["X"]["A1"], vk_json["vk_gamma_2"][0][1]);
+    }
+
+    #[test]
+    fn test_validate_vk_arity() {
+        let vk_json = include_str!("../test/data/r0_test_vk.json");
+        assert!(validate_vk_arity(vk_json, 5).is_ok());
+        assert!(validate_vk_arity(vk_json, 4).is_err());
+
+        let mut json: serde_json::Value = serde_json::from_str(vk_json).unwrap();
+        json["IC"].as_array_mut().unwrap().pop();
+        let err = validate_vk_arity(&json.to_string(), 5).unwrap_err();
+        assert!(err.to_string().contains("IC points"));
+
+        assert!(validate_vk_arity("{}", 5).is_err());
+    }
+
+    #[test]
+    fn test_verification_key_from_bytes() {
+        let vk = load_verification_key();
+        let from_bytes = VerificationKey::from_bytes(
+            &vk.vk_alpha_g1,
+            &vk.vk_beta_g2,
+            &vk.vk_gamma_g2,
+            &vk.vk_delta_g2,
+            vk.vk_ic,
+        )
+        .unwrap();
+        assert_eq!(from_bytes, vk);
+
+        let from = |alpha: &[u8; 64], beta: &[u8; 128], ic: &[[u8; 64]]| {
+            VerificationKey::from_bytes(alpha, beta, &vk.vk_gamma_g2, &vk.vk_delta_g2, ic)
+                .map(|_| ())
+        };
+        let rejected = |result| matches!(result, Err(Risc0SolanaError::InvalidVerificationKey));
+
+        assert!(rejected(from(&vk.vk_alpha_g1, &vk.vk_beta_g2, &[])));
+
+        let mut off_curve = vk.vk_alpha_g1;
+        off_curve[63] ^= 1;
+        assert!(rejected(from(&off_curve, &vk.vk_beta_g2, vk.vk_ic)));
+
+        // `x + q` encodes the same field element as `x` but is not canonical.
+        let mut unreduced = vk.vk_ic.to_vec();
+        let x = num_bigint::BigUint::from_bytes_be(&unreduced[1][..32])
+            + num_bigint::BigUint::from_bytes_be(&BASE_FIELD_MODULUS_Q);
+        unreduced[1][..32].copy_from_slice(&x.to_bytes_be());
+        assert!(rejected(from(&vk.vk_alpha_g1, &vk.vk_beta_g2, &unreduced)));
+
+        let mut beta = vk.vk_beta_g2;
+        beta[..32].copy_from_slice(&BASE_FIELD_MODULUS_Q);
+        assert!(rejected(from(&vk.vk_alpha_g1, &beta, vk.vk_ic)));
+    }
+
+    #[test]
+    fn test_owned_verification_key_borsh_roundtrip() {
+        let (_, proof, public_inputs) = load_receipt_and_extract_data();
+        let vk = load_verification_key();
+
+        let bytes = borsh::to_vec(&vk).unwrap();
+        let owned: OwnedVerificationKey = borsh::from_slice(&bytes).unwrap();
+        assert_eq!(owned, OwnedVerificationKey::from(&vk));
+        assert_eq!(owned.as_ref(), vk);
+        assert_eq!(borsh::to_vec(&owned).unwrap(), bytes);
+
+        assert!(verify_proof(&proof, &public_inputs, &owned.as_ref()).is_ok());
+    }
+
+    #[test]
+    fn test_ic_points() {
+        let vk = load_verification_key();
+        let points = vk.ic_points().unwrap();
+        assert_eq!(points.len(), 6);
+        assert!(points.iter().all(|p| p.is_on_curve()));
+
+        let mut vk_ic = vk.vk_ic.to_vec();
+        vk_ic[3][63] ^= 1;
+        let invalid_vk = VerificationKey {
+            vk_ic: &vk_ic,
+            ..vk
+        };
+        assert!(invalid_vk.ic_points().is_err());
+    }
+
+    #[test]
+    fn test_diagnose_mismatch() {
+        let (_, proof, public_inputs) = load_receipt_and_extract_data();
+        let vk = load_verification_key();
+        assert_eq!(
+            diagnose_mismatch(&proof, &public_inputs, &vk),
+            MismatchHint::Verified
+        );
+
+        let mut wrong_vk = vk.clone();
+        wrong_vk.vk_delta_g2 = vk.vk_gamma_g2;
+        assert_eq!(
+            diagnose_mismatch(&proof, &public_inputs, &wrong_vk),
+            MismatchHint::LikelyWrongKey
+        );
+
+        let mut short_vk = vk.clone();
+        short_vk.vk_ic = &vk.vk_ic[..4];
+        assert_eq!(
+            diagnose_mismatch(&proof, &public_inputs, &short_vk),
+            MismatchHint::InputCountMismatch {
+                expected: 3,
+                actual: 5
+            }
+        );
+    }
+
+    #[test]
+    fn test_verify_proof_with_invalid_vk_ic_length() {
+        let (_, proof, public_inputs) = load_receipt_and_extract_data();
+        let mut vk = load_verification_key();
+
+        vk.vk_ic = &vk.vk_ic[..vk.vk_ic.len() - 1]; // Remove one element
+
+        let result = verify_proof(&proof, &public_inputs, &vk);
+
+        assert!(matches!(
+            result,
+            Err(ProgramError::Custom(code)) if code == Risc0SolanaError::InvalidPublicInput as u32
+        ));
+    }
+
+    #[test]
+    fn test_public_inputs() {
+        let (_, _, public_inputs) = load_receipt_and_extract_data();
+        println!("{:?}", public_inputs);
+
+        // Test roundtrip
+        let exported_json = serde_json::to_string(&public_inputs).unwrap();
+        println!("{:?}", exported_json);
+        let reimported_inputs: PublicInputs<5> = serde_json::from_str(&exported_json).unwrap();
+        assert_eq!(
+            public_inputs, reimported_inputs,
+            "Public Inputs roundtrip failed"
+        );
+    }
+
+    #[test]
+    fn test_public_inputs_to_decimal_strings() {
+        let (_, _, public_inputs) = load_receipt_and_extract_data();
+        let strings = public_inputs.to_decimal_strings();
+
+        assert_eq!(strings.len(), 5);
+        assert_eq!(
+            serde_json::to_value(&public_inputs).unwrap(),
+            serde_json::json!(strings)
+        );
+        assert_eq!(PublicInputs::<5>::try_from(strings).unwrap(), public_inputs);
+    }
+
+    #[test]
+    fn test_public_inputs_try_from_bytes() {
+        let (_, _, public_inputs) = load_receipt_and_extract_data();
+        let bytes = public_inputs.inputs.concat();
+        let invalid = ProgramError::Custom(Risc0SolanaError::INVALID_PUBLIC_INPUT_CODE);
+
+        assert_eq!(PublicInputs::<5>::try_from(&bytes[..]), Ok(public_inputs));
+        assert_eq!(
+            PublicInputs::<5>::try_from(&bytes[..159]),
+            Err(invalid.clone())
+        );
+        assert_eq!(
+            PublicInputs::<4>::try_from(&bytes[..]),
+            Err(invalid.clone())
+        );
+
+        let mut out_of_range = bytes.clone();
+        out_of_range[64..96].copy_from_slice(&BASE_FIELD_MODULUS_Q);
+        assert_eq!(PublicInputs::<5>::try_from(&out_of_range[..]), Err(invalid));
+    }
+
+    #[test]
+    fn test_public_inputs_from_le_bytes() {
+        let (_, proof, public_inputs) = load_receipt_and_extract_data();
+
+        let mut le = public_inputs.inputs;
+        for input in le.iter_mut() {
+            input.reverse();
+        }
+        let from_le = PublicInputs::from_le_bytes(&le);
+        assert_eq!(from_le, public_inputs);
+        assert_eq!(PublicInputs::from_le_bytes(&from_le.inputs).inputs, le);
+
+        let vk = load_verification_key();
+        assert!(verify_proof(&proof, &from_le, &vk).is_ok());
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    fn test_public_inputs_looks_malformed() {
+        let zeros = PublicInputs::<5> {
+            inputs: [[0u8; 32]; 5],
+        };
+        assert!(zeros.looks_malformed(), "All-zero inputs should be flagged");
+
+        let (_, _, public_inputs) = load_receipt_and_extract_data();
+        assert!(!public_inputs.looks_malformed());
+
+        let mut mixed_up = public_inputs.clone();
+        mixed_up.inputs[2] = mixed_up.inputs[0];
+        assert!(mixed_up.looks_malformed());
+    }
+
+    #[test]
+    fn test_public_inputs_storage_roundtrip() {
+        let (_, _, public_inputs) = load_receipt_and_extract_data();
+        let storage = PublicInputsStorage::from_public_inputs(&public_inputs);
+
+        let bytes = borsh::to_vec(&storage).unwrap();
+        assert_eq!(bytes.len(), 5 * 32);
+        assert_eq!(bytes, public_inputs.inputs.concat());
+
+        let decoded: PublicInputsStorage<5> = borsh::from_slice(&bytes).unwrap();
+        assert_eq!(decoded, storage);
+    }
+
+    #[test]
+    fn test_public_inputs_account_size() {
+        assert_eq!(public_inputs_account_size(5), 168);
+        assert_eq!(public_inputs_account_size(0), 8);
+    }
+
+    #[cfg(feature = "receipt")]
+    #[test]
+    fn test_verify_receipt_full() {
+        let (receipt, _, _) = load_receipt_and_extract_data();
+        let vk = load_verification_key();
+        let image_id = Digest::from(EXAMPLE_IMAGE_ID);
+
+        verify_receipt_full(
+            &receipt,
+            image_id,
+            &vk,
+            ALLOWED_CONTROL_ROOT,
+            BN254_IDENTITY_CONTROL_ID,
+        )
+        .unwrap();
+
+        assert!(verify_receipt_full(
+            &receipt,
+            Digest::ZERO,
+            &vk,
+            ALLOWED_CONTROL_ROOT,
+            BN254_IDENTITY_CONTROL_ID,
+        )
+        .is_err());
+    }
+
+    #[cfg(feature = "receipt")]
+    #[test]
+    fn test_verify_receipt() {
+        use risc0_zkvm::InnerReceipt;
+
+        let (receipt, _, _) = load_receipt_and_extract_data();
+        let vk = load_verification_key();
+
+        verify_receipt(
+            &receipt,
+            &vk,
+            ALLOWED_CONTROL_ROOT,
+            BN254_IDENTITY_CONTROL_ID,
+        )
+        .unwrap();
+
+        let mut tampered = receipt.clone();
+        if let InnerReceipt::Groth16(groth16) = &mut tampered.inner {
+            groth16.seal[255] ^= 1;
+        }
+        assert!(verify_receipt(
+            &tampered,
+            &vk,
+            ALLOWED_CONTROL_ROOT,
+            BN254_IDENTITY_CONTROL_ID
+        )
+        .is_err());
+    }
+
+    #[cfg(feature = "receipt")]
+    #[test]
+    fn test_is_dev_mode_receipt() {
+        use risc0_zkvm::{FakeReceipt, InnerReceipt, ReceiptClaim};
+
+        let (receipt, _, _) = load_receipt_and_extract_data();
+        let vk = load_verification_key();
+        let image_id = [7u8; 32];
+        let journal = b"dev".to_vec();
+        let claim = ReceiptClaim::ok(Digest::from(image_id), journal.clone());
+        let fake = Receipt::new(InnerReceipt::Fake(FakeReceipt::new(claim)), journal);
+
+        assert!(is_dev_mode_receipt(&fake));
+        assert!(!is_dev_mode_receipt(&receipt));
+
+        let err = verify_receipt_full(
+            &fake,
+            image_id,
+            &vk,
+            ALLOWED_CONTROL_ROOT,
+            BN254_IDENTITY_CONTROL_ID,
+        )
+        .unwrap_err();
+        assert_eq!(err.to_string(), "dev-mode receipt cannot be verified");
+    }
+
+    #[cfg(feature = "receipt")]
+    #[test]
+    fn test_verify_receipts() {
+        let (receipt, _, _) = load_receipt_and_extract_data();
+        let vk = load_verification_key();
+        let image_id = Digest::from(EXAMPLE_IMAGE_ID);
+
+        let mut tampered = receipt.clone();
+        tampered.journal.bytes[0] ^= 1;
+        let receipts = [receipt.clone(), tampered, receipt];
+
+        let report = verify_receipts(
+            &receipts,
+            image_id,
+            &vk,
+            ALLOWED_CONTROL_ROOT,
+            BN254_IDENTITY_CONTROL_ID,
+        );
+        assert_eq!(report.passed, 2);
+        assert_eq!(report.failed.len(), 1);
+        assert_eq!(report.failed[0].0, 1);
+    }
+
+    #[cfg(feature = "receipt")]
+    #[test]
+    fn test_verify_with_version_check() {
+        let (receipt, _, _) = load_receipt_and_extract_data();
+        let vk = load_verification_key();
+
+        verify_with_version_check(
+            &receipt,
+            risc0_zkvm::VERSION,
+            &vk,
+            ALLOWED_CONTROL_ROOT,
+            BN254_IDENTITY_CONTROL_ID,
+        )
+        .unwrap();
+
+        let err = verify_with_version_check(
+            &receipt,
+            "0.0.0-stale",
+            &vk,
+            ALLOWED_CONTROL_ROOT,
+            BN254_IDENTITY_CONTROL_ID,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("version mismatch"));
+    }
+
+    #[cfg(feature = "receipt")]
+    #[test]
+    fn test_public_inputs_from_receipt() {
+        let (receipt, _, expected) = load_receipt_and_extract_data();
+        let public_inputs =
+            public_inputs_from_receipt(&receipt, ALLOWED_CONTROL_ROOT, BN254_IDENTITY_CONTROL_ID)
+                .unwrap();
+        assert_eq!(public_inputs, expected);
+    }
+
+    #[test]
+    fn test_same_claim() {
+        let (_, _, inputs) = load_receipt_and_extract_data();
+
+        let mut other_root = inputs.clone();
+        other_root.inputs[0] = [1u8; 32];
+        assert!(same_claim(&inputs, &other_root));
+
+        let other_claim =
+            public_inputs([7u8; 32], ALLOWED_CONTROL_ROOT, BN254_IDENTITY_CONTROL_ID).unwrap();
+        assert!(!same_claim(&inputs, &other_claim));
+    }
+
+    #[test]
+    fn test_proof() {
+        let (_, proof, _) = load_receipt_and_extract_data();
+        println!("{:?}", proof);
+
+        // Convert to bytes
+        let proof_bytes = proof.to_bytes();
+
+        println!("PROOF: {:?}", proof_bytes);
+
+        // Check that we have 256 bytes
+        assert_eq!(proof_bytes.len(), 256);
+
+        // Test roundtrip
+        let exported_json = serde_json::to_string(&proof).unwrap();
+        let reimported_proof: Proof = serde_json::from_str(&exported_json).unwrap();
+        assert_eq!(proof, reimported_proof, "Proof roundtrip failed");
+
+        println!("Proof bytes: {:?}", proof_bytes);
+    }
+
+    #[test]
+    fn test_proof_variants() {
+        let (receipt, proof, public_inputs) = load_receipt_and_extract_data();
+        let vk = load_verification_key();
+        let seal = &receipt.inner.groth16().unwrap().seal;
+
+        let (negated, raw) = proof_variants(seal).unwrap();
+        assert_eq!(negated, proof);
+        assert_eq!(
+            raw.pi_a[..32],
+            negated.pi_a[..32],
+            "x coordinate should match"
+        );
+        assert_ne!(
+            raw.pi_a[32..],
+            negated.pi_a[32..],
+            "y coordinate should differ"
+        );
+        assert_eq!(raw.pi_b, negated.pi_b);
+        assert_eq!(raw.pi_c, negated.pi_c);
+
+        assert!(verify_proof(&negated, &public_inputs, &vk).is_ok());
+        assert!(verify_proof(&raw, &public_inputs, &vk).is_err());
+
+        assert!(proof_variants(&seal[..255]).is_err());
+    }
+
+    #[test]
+    fn test_verify_ark() {
+        use ark_serialize::CanonicalDeserialize;
+        use solana_program::alt_bn128::compression::prelude::convert_endianness;
+
+        let (_, proof, public_inputs) = load_receipt_and_extract_data();
+        let vk = load_verification_key();
+
+        let g1 = |bytes: &[u8; 64]| {
+            ark_bn254::G1Affine::deserialize_uncompressed(&convert_endianness::<32, 64>(bytes)[..])
+                .unwrap()
+        };
+        let pi_b = ark_bn254::G2Affine::deserialize_uncompressed(
+            &convert_endianness::<64, 128>(&proof.pi_b)[..],
+        )
+        .unwrap();
+
+        let ark_proof = (g1(&proof.pi_a), pi_b, g1(&proof.pi_c));
+        assert!(verify_ark(ark_proof, &public_inputs, &vk).is_ok());
+
+        let wrong_a = (-ark_proof.0, ark_proof.1, ark_proof.2);
+        assert!(verify_ark(wrong_a, &public_inputs, &vk).is_err());
+    }
+
+    #[test]
+    fn test_proof_byte_len() {
+        assert_eq!(Proof::SERIALIZED_LEN, 256);
+        assert_eq!(Proof::COMPRESSED_LEN, 128);
+        assert_eq!(Proof::byte_len(false), Proof::SERIALIZED_LEN);
+        assert_eq!(Proof::byte_len(true), Proof::COMPRESSED_LEN);
+
+        let (_, proof, _) = load_receipt_and_extract_data();
+        assert_eq!(proof.to_bytes().len(), Proof::SERIALIZED_LEN);
+    }
+
+    #[test]
+    fn test_proof_from_bytes() {
+        let (_, proof, _) = load_receipt_and_extract_data();
+        let bytes = proof.to_bytes();
+        assert_eq!(Proof::from_bytes(&bytes), proof);
+        assert_eq!(Proof::try_from(&bytes[..]), Ok(proof));
+
+        assert_eq!(
+            Proof::try_from(&bytes[..255]),
+            Err(ProgramError::InvalidInstructionData)
+        );
+        assert_eq!(
+            Proof::try_from(&[0u8; 257][..]),
+            Err(ProgramError::InvalidInstructionData)
+        );
+    }
+
+    #[test]
+    fn test_proof_fingerprint() {
+        let (_, proof, _) = load_receipt_and_extract_data();
+        let same = proof.clone();
+        assert_eq!(proof.fingerprint(), same.fingerprint());
+        assert_eq!(proof.fingerprint(), hashv(&[&proof.to_bytes()]).to_bytes());
+
+        let mut other = proof.clone();
+        other.pi_c[63] ^= 1;
+        assert_ne!(proof.fingerprint(), other.fingerprint());
+    }
+
+    #[test]
+    pub fn test_verify() {
+        let (_, proof, public_inputs) = load_receipt_and_extract_data();
+        let vk = load_verification_key();
+        let res = verify_proof(&proof, &public_inputs, &vk);
+        assert!(res.is_ok(), "Verification failed");
+    }
+
+    #[test]
+    fn test_verify_proof_with_claim() {
+        let (_, proof, public_inputs) = load_receipt_and_extract_data();
+        let vk = load_verification_key();
+        let claim_digest = get_claim_digest();
+
+        assert_eq!(DEFAULT_ALLOWED_CONTROL_ROOT, ALLOWED_CONTROL_ROOT);
+        assert_eq!(DEFAULT_BN254_IDENTITY_CONTROL_ID, BN254_IDENTITY_CONTROL_ID);
+        assert_eq!(
+            verify_proof_with_claim(&proof, claim_digest, &vk),
+            verify_proof(&proof, &public_inputs, &vk)
+        );
+        assert!(verify_proof_with_claim(&proof, claim_digest, &vk).is_ok());
+        assert!(verify_proof_with_claim(&proof, [0u8; 32], &vk).is_err());
+    }
+
+    #[test]
+    fn test_verify_with_expected_journal() {
+        let (receipt, proof, _) = load_receipt_and_extract_data();
+        let vk = load_verification_key();
+        let image_id = image_id_to_bytes(EXAMPLE_IMAGE_ID);
+        let journal = receipt.journal.bytes;
+
+        assert_eq!(
+            verify_with_expected_journal(&proof, &image_id, &journal, &vk),
+            Ok(())
+        );
+
+        let mut other_journal = journal.clone();
+        other_journal[0] ^= 1;
+        assert_eq!(
+            verify_with_expected_journal(&proof, &image_id, &other_journal, &vk),
+            Err(ProgramError::Custom(
+                Risc0SolanaError::PAIRING_MISMATCH_CODE
+            ))
+        );
+    }
+
+    #[test]
+    fn test_verify_proof_any_identity() {
+        let (_, proof, _) = load_receipt_and_extract_data();
+        let vk = load_verification_key();
+        let claim_digest = get_claim_digest();
+        let other_id = "0000000000000000000000000000000000000000000000000000000000000001";
+
+        let verify = |ids: &[&str]| {
+            verify_proof_any_identity(&proof, claim_digest, ALLOWED_CONTROL_ROOT, ids, &vk)
+        };
+        assert_eq!(verify(&[other_id, BN254_IDENTITY_CONTROL_ID]), Ok(()));
+        assert_eq!(
+            verify(&[other_id]),
+            Err(ProgramError::Custom(
+                Risc0SolanaError::PAIRING_MISMATCH_CODE
+            ))
+        );
+        assert_eq!(
+            verify(&[]),
+            Err(ProgramError::Custom(
+                Risc0SolanaError::INVALID_PUBLIC_INPUT_CODE
+            ))
+        );
+    }
+
+    #[test]
+    fn test_verify_with_split_claim() {
+        let (_, proof, public_inputs) = load_receipt_and_extract_data();
+        let vk = load_verification_key();
+        let [a0, a1, c0, c1, id] = public_inputs.inputs;
+
+        assert_eq!(
+            verify_with_split_claim(&proof, (a0, a1), (c0, c1), id, &vk),
+            verify_proof(&proof, &public_inputs, &vk)
+        );
+        assert!(verify_with_split_claim(&proof, (a0, a1), (c0, c1), id, &vk).is_ok());
+        assert!(verify_with_split_claim(&proof, (a0, a1), (c1, c0), id, &vk).is_err());
+    }
+
+    #[test]
+    fn test_decompress_g2_checked() {
+        use ark_serialize::CanonicalSerialize;
+
+        let (_, proof, _) = load_receipt_and_extract_data();
+        let compressed = compress_g2_be(&proof.pi_b);
+        assert_eq!(decompress_g2_checked(&compressed).unwrap(), proof.pi_b);
+        assert_eq!(decompress_g2_checked(&[0u8; 64]).unwrap(), [0u8; 128]);
+        assert!(matches!(
+            decompress_g2_checked(&[0xFF; 64]),
+            Err(Risc0SolanaError::G2CompressionError)
+        ));
+
+        // Most points on the twist are outside the subgroup; take the first one found.
+        let outside = (1u64..)
+            .filter_map(|x| {
+                let x = ark_bn254::Fq2::new(x.into(), 0u64.into());
+                ark_bn254::G2Affine::get_point_from_x_unchecked(x, false)
+            })
+            .find(|point| !point.is_in_correct_subgroup_assuming_on_curve())
+            .unwrap();
+        let mut le = [0u8; 64];
+        outside
+            .serialize_with_mode(&mut le[..], Compress::Yes)
+            .unwrap();
+        let compressed = convert_endianness::<64, 64>(&le);
+
+        assert!(matches!(
+            decompress_g2_checked(&compressed),
+            Err(Risc0SolanaError::G2SubgroupError)
+        ));
+    }
+
+    #[test]
+    fn test_verify_from_packed_account() {
+        let (_, proof, public_inputs) = load_receipt_and_extract_data();
+        let vk = load_verification_key();
+        let mut data = [
+            compress_g1_be(&proof.pi_a).as_slice(),
+            &compress_g2_be(&proof.pi_b),
+            &compress_g1_be(&proof.pi_c),
+            &public_inputs.inputs.concat(),
+        ]
+        .concat();
+        assert_eq!(data.len(), PACKED_ACCOUNT_LEN);
+
+        let verify = |data: &mut [u8]| {
+            let key = solana_program::pubkey::Pubkey::new_unique();
+            let mut lamports = 0;
+            let account = AccountInfo::new(&key, false, false, &mut lamports, data, &key, false, 0);
+            verify_from_packed_account(&account, &vk)
+        };
+        assert_eq!(verify(&mut data), Ok(()));
+
+        data[PACKED_PUBLIC_INPUTS_OFFSET + 64] ^= 1;
+        assert_eq!(
+            verify(&mut data),
+            Err(ProgramError::Custom(
+                Risc0SolanaError::PAIRING_MISMATCH_CODE
+            ))
+        );
+
+        data[32..96].fill(0xFF);
+        assert_eq!(
+            verify(&mut data),
+            Err(ProgramError::Custom(
+                Risc0SolanaError::G2_COMPRESSION_ERROR_CODE
+            ))
+        );
+        assert_eq!(
+            verify(&mut data[..PACKED_ACCOUNT_LEN - 1]),
+            Err(ProgramError::InvalidAccountData)
+        );
+    }
+
+    #[test]
+    fn test_verify_rejects_infinity_pi_b() {
+        let (_, mut proof, public_inputs) = load_receipt_and_extract_data();
+        let vk = load_verification_key();
+        proof.pi_b = [0u8; 128];
+
+        let expected = Err(ProgramError::Custom(
+            Risc0SolanaError::VERIFICATION_ERROR_CODE,
+        ));
+        assert_eq!(verify_proof(&proof, &public_inputs, &vk), expected);
+        assert_eq!(verify_proof_large(&proof, &public_inputs, &vk), expected);
+        assert_eq!(verify_proof_trusted(&proof, &public_inputs, &vk), expected);
+    }
+
+    #[test]
+    fn test_verify_pairing_mismatch_vs_error() {
+        let (_, proof, public_inputs) = load_receipt_and_extract_data();
+        let vk = load_verification_key();
+
+        let mut wrong_inputs = public_inputs.clone();
+        wrong_inputs.inputs[2] = [0u8; 32];
+        assert_eq!(
+            verify_proof(&proof, &wrong_inputs, &vk),
+            Err(ProgramError::Custom(
+                Risc0SolanaError::PAIRING_MISMATCH_CODE
+            ))
+        );
+
+        let mut off_curve = proof.clone();
+        off_curve.pi_b[127] ^= 1;
+        assert_eq!(
+            verify_proof(&off_curve, &public_inputs, &vk),
+            Err(ProgramError::Custom(Risc0SolanaError::PAIRING_ERROR_CODE))
+        );
+    }
+
+    #[test]
+    fn test_verify_from_files() {
+        let (_, mut proof, public_inputs) = load_receipt_and_extract_data();
+        // The files hold the proof as emitted by the prover, with `pi_a` not yet negated.
+        proof.pi_a = negate_g1(&proof.pi_a).unwrap();
+
+        let dir = std::env::temp_dir().join("risc0_solana_verify_from_files");
+        std::fs::create_dir_all(&dir).unwrap();
+        let vk_path = dir.join("vk.json");
+        let proof_path = dir.join("proof.json");
+        let public_path = dir.join("public.json");
+
+        std::fs::write(&vk_path, include_str!("../test/data/r0_test_vk.json")).unwrap();
+        std::fs::write(&proof_path, serde_json::to_string(&proof).unwrap()).unwrap();
+        std::fs::write(&public_path, serde_json::to_string(&public_inputs).unwrap()).unwrap();
+
+        let result = verify_from_files(
+            vk_path.to_str().unwrap(),
+            proof_path.to_str().unwrap(),
+            public_path.to_str().unwrap(),
+        );
+        assert!(result.is_ok(), "Verification from files failed");
+
+        let mut wrong_inputs = public_inputs.clone();
+        wrong_inputs.inputs[4] = [0u8; 32];
+        std::fs::write(&public_path, serde_json::to_string(&wrong_inputs).unwrap()).unwrap();
+
+        let result = verify_from_files(
+            vk_path.to_str().unwrap(),
+            proof_path.to_str().unwrap(),
+            public_path.to_str().unwrap(),
+        );
+        assert!(
+            result.is_err(),
+            "Verification should fail with wrong inputs"
+        );
+    }
+
+    #[test]
+    fn test_pairing_failure_witness() {
+        let (_, proof, public_inputs) = load_receipt_and_extract_data();
+        let vk = load_verification_key();
+        assert_eq!(pairing_failure_witness(&proof, &public_inputs, &vk), None);
+
+        let mut swapped_c = proof.clone();
+        swapped_c.pi_c = negate_g1(&proof.pi_c).unwrap();
+        assert_eq!(
+            pairing_failure_witness(&swapped_c, &public_inputs, &vk),
+            Some(PairingTerm::ProofCDelta)
+        );
+
+        let mut raw_a = proof.clone();
+        raw_a.pi_a = negate_g1(&proof.pi_a).unwrap();
+        assert_eq!(
+            pairing_failure_witness(&raw_a, &public_inputs, &vk),
+            Some(PairingTerm::ProofAB)
+        );
+
+        let mut off_curve = proof.clone();
+        off_curve.pi_c[63] ^= 1;
+        assert_eq!(
+            pairing_failure_witness(&off_curve, &public_inputs, &vk),
+            Some(PairingTerm::ProofCDelta)
+        );
+    }
+
+    #[cfg(feature = "receipt")]
+    #[test]
+    fn test_build_verify_instruction_data() {
+        use solana_program::alt_bn128::compression::prelude::{
+            alt_bn128_g1_decompress, alt_bn128_g2_decompress,
+        };
+
+        let (receipt, proof, _) = load_receipt_and_extract_data();
+        let data = build_verify_instruction_data(&receipt).unwrap();
+
+        assert_eq!(data.len(), 160);
+        assert_eq!(data[..32], get_claim_digest());
+        assert_eq!(alt_bn128_g1_decompress(&data[32..64]).unwrap(), proof.pi_a);
+        assert_eq!(alt_bn128_g2_decompress(&data[64..128]).unwrap(), proof.pi_b);
+        assert_eq!(alt_bn128_g1_decompress(&data[128..]).unwrap(), proof.pi_c);
+    }
+
+    #[test]
+    fn test_compressed_point_info() {
+        use ark_serialize::CanonicalSerialize;
+        use solana_program::alt_bn128::compression::prelude::convert_endianness;
+
+        let (_, proof, _) = load_receipt_and_extract_data();
+
+        let (sign, infinity) = compressed_point_info(&compress_g1_be(&proof.pi_a));
+        assert!(!infinity);
+        let negated = compress_g1_be(&negate_g1(&proof.pi_a).unwrap());
+        assert_eq!(compressed_point_info(&negated), (!sign, false));
+
+        let (_, infinity) = compressed_point_info(&compress_g2_be(&proof.pi_b));
+        assert!(!infinity);
+
+        let mut compressed = [0u8; 32];
+        ark_bn254::G1Affine::identity()
+            .serialize_compressed(&mut compressed[..])
+            .unwrap();
+        let identity = convert_endianness::<32, 32>(&compressed);
+        assert_eq!(compressed_point_info(&identity), (false, true));
+    }
+
+    #[test]
+    fn test_proofs_equal_mod_endianness() {
+        use solana_program::alt_bn128::compression::prelude::convert_endianness;
+
+        let (_, proof, _) = load_receipt_and_extract_data();
+        let flipped = Proof {
+            pi_a: convert_endianness::<32, 64>(&proof.pi_a),
+            pi_b: convert_endianness::<64, 128>(&proof.pi_b),
+            pi_c: convert_endianness::<32, 64>(&proof.pi_c),
+        };
+        assert_ne!(proof, flipped);
+        assert!(proofs_equal_mod_endianness(&proof, &flipped));
+        assert!(proofs_equal_mod_endianness(&flipped, &proof));
+
+        let mut other = proof.clone();
+        other.pi_c = negate_g1(&proof.pi_c).unwrap();
+        assert!(!proofs_equal_mod_endianness(&proof, &other));
+
+        let mut undecodable = proof.clone();
+        undecodable.pi_b = [0u8; 128];
+        let twin = undecodable.clone();
+        assert!(proofs_equal_mod_endianness(&undecodable, &twin));
+    }
+
+    #[test]
+    fn test_dump_failure_bundle() {
+        let (_, proof, mut public_inputs) = load_receipt_and_extract_data();
+        let vk = load_verification_key();
+        public_inputs.inputs[0] = [0u8; 32];
+
+        let dir = std::env::temp_dir().join("risc0_solana_failure_bundle");
+        let _ = std::fs::remove_dir_all(&dir);
+        dump_failure_bundle(dir.to_str().unwrap(), &proof, &public_inputs, &vk).unwrap();
+
+        for file in ["proof.json", "public.json", "vk.json", "report.txt"] {
+            assert!(dir.join(file).exists(), "{} was not written", file);
+        }
+        let report = std::fs::read_to_string(dir.join("report.txt")).unwrap();
+        assert!(
+            report.starts_with("result: failed"),
+            "Unexpected report: {}",
+            report
+        );
+
+        let reimported: PublicInputs<5> =
+            serde_json::from_str(&std::fs::read_to_string(dir.join("public.json")).unwrap())
+                .unwrap();
+        assert_eq!(reimported, public_inputs);
     }
 
-    fn load_verification_key() -> VerificationKey<'static> {
-        let vk_json_str = include_str!("../test/data/r0_test_vk.json");
-        serde_json::from_str(vk_json_str).unwrap()
+    #[test]
+    fn test_verify_proof_pinned() {
+        let (_, proof, public_inputs) = load_receipt_and_extract_data();
+        let vk = load_verification_key();
+        assert_eq!(vk, RISC0_VERIFICATION_KEY);
+        assert!(verify_proof_pinned(&proof, &public_inputs, &vk, true).is_ok());
+
+        let mut other_vk = vk.clone();
+        other_vk.vk_gamma_g2 = vk.vk_delta_g2;
+        let result = verify_proof_pinned(&proof, &public_inputs, &other_vk, true);
+        assert!(matches!(
+            result,
+            Err(ProgramError::Custom(code)) if code == Risc0SolanaError::UnexpectedVerificationKey as u32
+        ));
+
+        // Without pinning the key is used as-is and fails in the pairing check instead.
+        let result = verify_proof_pinned(&proof, &public_inputs, &other_vk, false);
+        assert!(matches!(
+            result,
+            Err(ProgramError::Custom(code)) if code == Risc0SolanaError::PairingMismatch as u32
+        ));
     }
 
     #[test]
-    fn test_convert_g1_invalid_z() {
-        let values = vec![
-            "1".to_string(), // x
-            "2".to_string(), // y
-            "0".to_string(), // z (invalid)
-        ];
+    fn test_verify_with_key_and_digest_check() {
+        let (_, proof, public_inputs) = load_receipt_and_extract_data();
+        let vk = load_verification_key();
+        let digest = vk.digest();
+        assert_eq!(digest, hashv(&[&borsh::to_vec(&vk).unwrap()]).to_bytes());
 
-        let result = convert_g1(&values);
+        assert!(verify_with_key_and_digest_check(&proof, &public_inputs, &vk, &digest).is_ok());
 
-        assert!(
-            result.is_err(),
-            "Expected error due to invalid Z coordinate"
+        let mut other_vk = vk.clone();
+        other_vk.vk_alpha_g1 = vk.vk_ic[0];
+        assert_eq!(
+            verify_with_key_and_digest_check(&proof, &public_inputs, &other_vk, &digest),
+            Err(ProgramError::Custom(
+                Risc0SolanaError::UnexpectedVerificationKey as u32
+            ))
         );
+    }
+
+    #[test]
+    fn test_verify_blob() {
+        let (_, proof, public_inputs) = load_receipt_and_extract_data();
+        let vk = load_verification_key();
+
+        let bundle = ProofBundle {
+            proof,
+            public_inputs,
+        };
+        let blob = borsh::to_vec(&bundle).unwrap();
+        assert_eq!(blob.len(), 256 + 5 * 32);
+        assert!(verify_blob(&blob, &vk).is_ok(), "Blob verification failed");
+
         assert_eq!(
-            result.unwrap_err().to_string(),
-            "Invalid G1 point: Z coordinate is not 1 (found 0)"
+            verify_blob(&blob[..blob.len() - 1], &vk),
+            Err(ProgramError::InvalidInstructionData)
         );
     }
 
     #[test]
-    fn test_convert_g2_invalid_z() {
-        let values = vec![
-            vec!["1".to_string(), "2".to_string()], // x
-            vec!["3".to_string(), "4".to_string()], // y
-            vec!["0".to_string(), "0".to_string()], // z (invalid)
+    fn test_verify_proofs_batched() {
+        let (_, proof, public_inputs) = load_receipt_and_extract_data();
+        let vk = load_verification_key();
+
+        assert_eq!(verify_proofs_batched::<5>(&[], &vk), Ok(()));
+        let batch = [(&proof, &public_inputs); 3];
+        assert_eq!(verify_proofs_batched(&batch, &vk), Ok(()));
+
+        let mut bad_proof = proof.clone();
+        bad_proof.pi_a = negate_g1(&proof.pi_a).unwrap();
+        let batch = [
+            (&proof, &public_inputs),
+            (&bad_proof, &public_inputs),
+            (&proof, &public_inputs),
         ];
+        let err = verify_proofs_batched(&batch, &vk).unwrap_err();
+        assert_eq!(err.index, Some(1));
+        assert_eq!(
+            err.error,
+            ProgramError::Custom(Risc0SolanaError::PAIRING_MISMATCH_CODE)
+        );
 
-        let result = convert_g2(&values);
+        let mut out_of_range = public_inputs.clone();
+        out_of_range.inputs[0] = BASE_FIELD_MODULUS_Q;
+        let batch = [
+            (&proof, &public_inputs),
+            (&proof, &public_inputs),
+            (&proof, &out_of_range),
+        ];
+        let err = verify_proofs_batched(&batch, &vk).unwrap_err();
+        assert_eq!(err.index, Some(2));
 
-        assert!(
-            result.is_err(),
-            "Expected error due to invalid Z coordinate"
+        assert_eq!(
+            err.error,
+            ProgramError::Custom(Risc0SolanaError::INVALID_PUBLIC_INPUT_CODE)
         );
+
+        let mut infinity_b = proof.clone();
+        infinity_b.pi_b = [0u8; 128];
+        let batch = [(&proof, &public_inputs), (&infinity_b, &public_inputs)];
+        let err = verify_proofs_batched(&batch, &vk).unwrap_err();
+        assert_eq!(err.index, Some(1));
         assert_eq!(
-            result.unwrap_err().to_string(),
-            "Invalid G2 point: Z coordinate is not [1, 0] (found [0, 0])"
+            err.error,
+            ProgramError::Custom(Risc0SolanaError::VERIFICATION_ERROR_CODE)
         );
     }
 
     #[test]
-    fn test_import() {
-        let vk = load_verification_key();
-        println!("Verification Key: {:?}", vk);
+    fn test_estimated_compute_units() {
+        assert_eq!(estimated_compute_units(0), 36_364 + 3 * 12_121 + 10_000);
+        assert_eq!(estimated_compute_units(5), 103_597);
+        assert_eq!(
+            estimated_compute_units(6) - estimated_compute_units(5),
+            3_840 + 334
+        );
     }
 
     #[test]
-    fn test_roundtrip() {
+    fn test_needs_negation() {
+        let (receipt, negated, public_inputs) = load_receipt_and_extract_data();
         let vk = load_verification_key();
+        let (_, raw) = proof_variants(&receipt.inner.groth16().unwrap().seal).unwrap();
 
-        let exported_json = serde_json::to_string(&vk).unwrap();
-        let reimported_vk: VerificationKey = serde_json::from_str(&exported_json).unwrap();
+        assert!(needs_negation(&raw, &public_inputs, &vk));
+        assert!(!needs_negation(&negated, &public_inputs, &vk));
 
-        assert_eq!(vk, reimported_vk, "Roundtrip serialization failed");
+        let mut wrong_inputs = public_inputs.clone();
+        wrong_inputs.inputs[2] = [0u8; 32];
+        assert!(!needs_negation(&raw, &wrong_inputs, &vk));
     }
 
     #[test]
-    fn test_verify_proof_with_invalid_vk_ic_length() {
+    fn test_verify_and_estimate() {
         let (_, proof, public_inputs) = load_receipt_and_extract_data();
-        let mut vk = load_verification_key();
+        let vk = load_verification_key();
 
-        vk.vk_ic = &vk.vk_ic[..vk.vk_ic.len() - 1]; // Remove one element
+        let (verified, compute_units) = verify_and_estimate(&proof, &public_inputs, &vk).unwrap();
+        assert!(verified);
+        assert!(compute_units > 0);
+        assert_eq!(compute_units, estimated_compute_units(5));
 
-        let result = verify_proof(&proof, &public_inputs, &vk);
+        let mut wrong_inputs = public_inputs.clone();
+        wrong_inputs.inputs[2] = [0u8; 32];
+        let (verified, _) = verify_and_estimate(&proof, &wrong_inputs, &vk).unwrap();
+        assert!(!verified);
 
-        assert!(matches!(
-            result,
-            Err(ProgramError::Custom(code)) if code == Risc0SolanaError::InvalidPublicInput as u32
-        ));
+        let four_inputs = PublicInputs {
+            inputs: [public_inputs.inputs[0]; 4],
+        };
+        assert!(verify_and_estimate(&proof, &four_inputs, &vk).is_err());
     }
 
     #[test]
-    fn test_public_inputs() {
-        let (_, _, public_inputs) = load_receipt_and_extract_data();
-        println!("{:?}", public_inputs);
+    fn test_verify_proof_large() {
+        let (_, proof, public_inputs) = load_receipt_and_extract_data();
+        let vk = load_verification_key();
+        assert!(verify_proof_large(&proof, &public_inputs, &vk).is_ok());
+
+        // A synthetic 20-input key: the real IC points come first, the extra inputs are zero
+        // and reuse the real points, so the prepared input and thus the proof are unchanged.
+        let ic: Vec<[u8; 64]> = (0..21).map(|i| vk.vk_ic[i % vk.vk_ic.len()]).collect();
+        let large_vk = VerificationKey {
+            nr_pubinputs: 20,
+            vk_ic: &ic,
+            ..vk.clone()
+        };
+        let mut inputs = [[0u8; 32]; 20];
+        inputs[..5].copy_from_slice(&public_inputs.inputs);
+        let mut large_inputs = PublicInputs { inputs };
 
-        // Test roundtrip
-        let exported_json = serde_json::to_string(&public_inputs).unwrap();
-        println!("{:?}", exported_json);
-        let reimported_inputs: PublicInputs<5> = serde_json::from_str(&exported_json).unwrap();
+        assert!(verify_proof_large(&proof, &large_inputs, &large_vk).is_ok());
+        assert!(verify_proof(&proof, &large_inputs, &large_vk).is_ok());
+
+        large_inputs.inputs[12] = [1u8; 32];
         assert_eq!(
-            public_inputs, reimported_inputs,
-            "Public Inputs roundtrip failed"
+            verify_proof_large(&proof, &large_inputs, &large_vk),
+            verify_proof(&proof, &large_inputs, &large_vk)
+        );
+        assert!(verify_proof_large(&proof, &large_inputs, &large_vk).is_err());
+
+        large_inputs.inputs[19] = BASE_FIELD_MODULUS_Q;
+        assert_eq!(
+            verify_proof_large(&proof, &large_inputs, &large_vk),
+            Err(ProgramError::Custom(
+                Risc0SolanaError::INVALID_PUBLIC_INPUT_CODE
+            ))
         );
     }
 
     #[test]
-    fn test_proof() {
-        let (_, proof, _) = load_receipt_and_extract_data();
-        println!("{:?}", proof);
-
-        // Convert to bytes
-        let proof_bytes = proof.to_bytes();
-
-        println!("PROOF: {:?}", proof_bytes);
-
-        // Check that we have 256 bytes
-        assert_eq!(proof_bytes.len(), 256);
+    fn test_verify_proof_trusted() {
+        let (_, proof, public_inputs) = load_receipt_and_extract_data();
+        let vk = load_verification_key();
 
-        // Test roundtrip
-        let exported_json = serde_json::to_string(&proof).unwrap();
-        let reimported_proof: Proof = serde_json::from_str(&exported_json).unwrap();
-        assert_eq!(proof, reimported_proof, "Proof roundtrip failed");
+        assert_eq!(
+            verify_proof_trusted(&proof, &public_inputs, &vk),
+            verify_proof(&proof, &public_inputs, &vk)
+        );
+        assert!(verify_proof_trusted(&proof, &public_inputs, &vk).is_ok());
 
-        println!("Proof bytes: {:?}", proof_bytes);
+        let mut wrong_inputs = public_inputs.clone();
+        wrong_inputs.inputs[0] = [0u8; 32];
+        assert_eq!(
+            verify_proof_trusted(&proof, &wrong_inputs, &vk),
+            verify_proof(&proof, &wrong_inputs, &vk)
+        );
     }
 
     #[test]
-    pub fn test_verify() {
-        let (_, proof, public_inputs) = load_receipt_and_extract_data();
+    fn test_verify_proof_logged() {
+        let (_, proof, _) = load_receipt_and_extract_data();
         let vk = load_verification_key();
-        let res = verify_proof(&proof, &public_inputs, &vk);
-        assert!(res.is_ok(), "Verification failed");
+        let claim_digest = get_claim_digest();
+
+        let logged = verify_proof_logged(
+            &proof,
+            claim_digest,
+            ALLOWED_CONTROL_ROOT,
+            BN254_IDENTITY_CONTROL_ID,
+            &vk,
+        )
+        .unwrap();
+        assert_eq!(
+            logged,
+            public_inputs(
+                claim_digest,
+                ALLOWED_CONTROL_ROOT,
+                BN254_IDENTITY_CONTROL_ID
+            )
+            .unwrap()
+        );
+
+        let result = verify_proof_logged(
+            &proof,
+            [0u8; 32],
+            ALLOWED_CONTROL_ROOT,
+            BN254_IDENTITY_CONTROL_ID,
+            &vk,
+        );
+        assert!(result.is_err());
     }
 
     #[test]
@@ -769,6 +3560,237 @@ mod test_lib {
             .unwrap()
     }
 
+    #[test]
+    fn test_example_image_id() {
+        let (receipt, _, _) = load_receipt_and_extract_data();
+        let image_id = image_id_to_bytes(EXAMPLE_IMAGE_ID);
+        assert_eq!(Digest::from(EXAMPLE_IMAGE_ID).as_bytes(), image_id);
+
+        let journal_digest = compute_journal_digest(&receipt.journal.bytes);
+        assert_eq!(
+            compute_claim_digest(&image_id, &journal_digest),
+            get_claim_digest()
+        );
+    }
+
+    #[test]
+    fn test_tags() {
+        let tag = |label: &str| hashv(&[label.as_bytes()]).to_bytes();
+        assert_eq!(OUTPUT_TAG, tag("risc0.Output"));
+        assert_eq!(RECEIPT_CLAIM_TAG, tag("risc0.ReceiptClaim"));
+        assert_eq!(SYSTEM_STATE_TAG, tag("risc0.SystemState"));
+
+        // merkle_root = 0 and pc = 0, followed by the number of digest fields.
+        let zero_state = hashv(&[
+            &SYSTEM_STATE_TAG,
+            &[0u8; 32],
+            &0u32.to_le_bytes(),
+            &1u16.to_le_bytes(),
+        ]);
+        assert_eq!(SYSTEM_STATE_ZERO_DIGEST, zero_state.to_bytes());
+    }
+
+    #[test]
+    fn test_compute_digests() {
+        let (receipt, _, _) = load_receipt_and_extract_data();
+        let claim = receipt.inner.groth16().unwrap().claim.as_value().unwrap();
+        let image_id: [u8; 32] = claim.pre.digest().try_into().unwrap();
+        let journal = &receipt.journal.bytes;
+
+        let (journal_digest, claim_digest) = compute_digests(&image_id, journal);
+
+        assert_eq!(journal_digest, compute_journal_digest(journal));
+        assert_eq!(
+            claim_digest,
+            compute_claim_digest(&image_id, &compute_journal_digest(journal))
+        );
+        assert_eq!(
+            claim_digest,
+            get_claim_digest(),
+            "Claim digest does not match the receipt"
+        );
+    }
+
+    #[test]
+    fn test_compute_claim_digest_with_assumptions() {
+        use risc0_zkvm::{MaybePruned, ReceiptClaim};
+
+        let image_id = [3u8; 32];
+        let journal = b"composite".to_vec();
+        let journal_digest = compute_journal_digest(&journal);
+        let assumptions_digest = [9u8; 32];
+
+        let mut claim = ReceiptClaim::ok(Digest::from(image_id), journal);
+        if let MaybePruned::Value(Some(output)) = &mut claim.output {
+            output.assumptions = MaybePruned::Pruned(Digest::from(assumptions_digest));
+        }
+        let expected: [u8; 32] = claim.digest().try_into().unwrap();
+
+        assert_eq!(
+            compute_claim_digest_with_assumptions(&image_id, &journal_digest, &assumptions_digest),
+            expected
+        );
+        assert_eq!(
+            compute_claim_digest_with_assumptions(&image_id, &journal_digest, &[0u8; 32]),
+            compute_claim_digest(&image_id, &journal_digest)
+        );
+    }
+
+    #[test]
+    fn test_public_inputs_from_journal_and_image() {
+        let (receipt, proof, expected) = load_receipt_and_extract_data();
+        let claim = receipt.inner.groth16().unwrap().claim.as_value().unwrap();
+        let image_id: [u8; 32] = claim.pre.digest().try_into().unwrap();
+
+        let public_inputs = PublicInputs::from_journal_and_image(
+            &image_id,
+            &receipt.journal.bytes,
+            ALLOWED_CONTROL_ROOT,
+            BN254_IDENTITY_CONTROL_ID,
+        )
+        .unwrap();
+        assert_eq!(public_inputs, expected);
+        assert!(verify_proof(&proof, &public_inputs, &load_verification_key()).is_ok());
+    }
+
+    #[test]
+    fn test_public_inputs_n_claims() {
+        let claim_a = get_claim_digest();
+        let mut claim_b = claim_a;
+        claim_b.reverse();
+
+        let single = public_inputs(claim_b, ALLOWED_CONTROL_ROOT, BN254_IDENTITY_CONTROL_ID)
+            .unwrap()
+            .inputs;
+        let multi = public_inputs_n_claims(
+            [claim_a, claim_b],
+            ALLOWED_CONTROL_ROOT,
+            BN254_IDENTITY_CONTROL_ID,
+        )
+        .unwrap()
+        .inputs;
+
+        assert_eq!(multi.len(), 7);
+        assert_eq!(multi[..2], single[..2], "Control root mismatch");
+        assert_eq!(multi[4..6], single[2..4], "Second claim mismatch");
+        assert_eq!(multi[6], single[4], "Identity control ID mismatch");
+
+        let (_, _, expected) = load_receipt_and_extract_data();
+        let one_claim: PublicInputs<5> =
+            public_inputs_n_claims([claim_a], ALLOWED_CONTROL_ROOT, BN254_IDENTITY_CONTROL_ID)
+                .unwrap()
+                .try_into()
+                .unwrap();
+        assert_eq!(one_claim, expected);
+
+        for (control_root, identity_id) in [
+            ("not hex", BN254_IDENTITY_CONTROL_ID),
+            ("8b6dcf11", BN254_IDENTITY_CONTROL_ID),
+            (ALLOWED_CONTROL_ROOT, "not hex"),
+            (ALLOWED_CONTROL_ROOT, &"ff".repeat(32)),
+        ] {
+            assert_eq!(
+                public_inputs_n_claims([claim_a], control_root, identity_id).unwrap_err(),
+                ProgramError::from(Risc0SolanaError::InvalidPublicInput)
+            );
+        }
+    }
+
+    #[test]
+    fn test_receipt_claim_preimage() {
+        let (receipt, _, _) = load_receipt_and_extract_data();
+        let claim = receipt.inner.groth16().unwrap().claim.as_value().unwrap();
+        let image_id: [u8; 32] = claim.pre.digest().try_into().unwrap();
+        let output =
+            compute_output_digest(&compute_journal_digest(&receipt.journal.bytes), &[0u8; 32]);
+
+        let preimage = receipt_claim_preimage(
+            &[0u8; 32],
+            &image_id,
+            &SYSTEM_STATE_ZERO_DIGEST,
+            &output,
+            0,
+            0,
+        );
+
+        assert_eq!(preimage.len(), 32 * 5 + 4 + 4 + 2);
+        assert_eq!(hashv(&[&preimage]).to_bytes(), get_claim_digest());
+    }
+
+    #[test]
+    fn test_compute_journal_digest_with_domain() {
+        let journal = [1u8, 0, 0, 120];
+        let plain = compute_journal_digest(&journal);
+        let app_a = compute_journal_digest_with_domain(b"app-a", &journal);
+        let app_b = compute_journal_digest_with_domain(b"app-b", &journal);
+
+        assert_ne!(plain, app_a);
+        assert_ne!(app_a, app_b);
+        assert_eq!(
+            app_a,
+            compute_journal_digest_with_domain(b"app-a", &journal),
+            "Digest should be deterministic"
+        );
+    }
+
+    #[test]
+    fn test_prepared_input_cache() {
+        let (_, proof, public_inputs) = load_receipt_and_extract_data();
+        let vk = load_verification_key();
+
+        let mut cache = PreparedInputCache::new();
+        let first = cache.prepare(&public_inputs, &vk).unwrap();
+        assert_eq!(cache.len(), 5);
+
+        let cached = cache.prepare(&public_inputs, &vk).unwrap();
+        assert_eq!(cache.len(), 5, "Second preparation should hit the cache");
+        assert_eq!(first, cached);
+        assert_eq!(
+            cached,
+            PreparedInputCache::new()
+                .prepare(&public_inputs, &vk)
+                .unwrap(),
+            "Cached result differs from fresh computation"
+        );
+
+        assert!(verify_with_cache(&mut cache, &proof, &public_inputs, &vk).is_ok());
+
+        let mut other_inputs = public_inputs.clone();
+        other_inputs.inputs[2] = [0u8; 32];
+        assert!(verify_with_cache(&mut cache, &proof, &other_inputs, &vk).is_err());
+        assert_eq!(cache.len(), 6);
+
+        let mut infinity_b = proof.clone();
+        infinity_b.pi_b = [0u8; 128];
+        assert!(verify_with_cache(&mut cache, &infinity_b, &public_inputs, &vk).is_err());
+        assert_eq!(
+            cache.len(),
+            6,
+            "pi_b at infinity should be rejected before preparing"
+        );
+    }
+
+    #[test]
+    fn test_prepare_public_inputs() {
+        let (_, _, public_inputs) = load_receipt_and_extract_data();
+        let vk = load_verification_key();
+
+        let prepared = prepare_public_inputs(&public_inputs, &vk).unwrap();
+        assert_eq!(
+            prepared,
+            PreparedInputCache::new()
+                .prepare(&public_inputs, &vk)
+                .unwrap()
+        );
+
+        let mut out_of_range = public_inputs.clone();
+        out_of_range.inputs[4] = BASE_FIELD_MODULUS_Q;
+        assert!(matches!(
+            prepare_public_inputs(&out_of_range, &vk),
+            Err(Risc0SolanaError::InvalidPublicInput)
+        ));
+    }
+
     #[test]
     fn test_verify_proof_vk_ic_length() {
         let (_, proof, public_inputs) = load_receipt_and_extract_data();
@@ -797,6 +3819,22 @@ mod test_lib {
         );
     }
 
+    #[test]
+    fn test_assert_within_window() {
+        let clock = Clock {
+            unix_timestamp: 1_700_000_000,
+            ..Clock::default()
+        };
+        let outside = Err(ProgramError::Custom(
+            Risc0SolanaError::OutsideValidityWindow as u32,
+        ));
+
+        assert!(assert_within_window(1_700_000_000, 60, &clock).is_ok());
+        assert!(assert_within_window(1_699_999_940, 60, &clock).is_ok());
+        assert_eq!(assert_within_window(1_699_999_939, 60, &clock), outside);
+        assert_eq!(assert_within_window(1_700_000_001, 60, &clock), outside);
+    }
+
     #[test]
     fn test_scalar_validity_check() {
         let valid_scalar = [0u8; 32];
@@ -813,6 +3851,63 @@ mod test_lib {
         assert!(is_scalar_valid(&below_q), "q-1 should be valid");
     }
 
+    #[cfg(feature = "diagnostic")]
+    #[test]
+    fn test_verify_proof_diagnostic() {
+        use solana_program::program_stubs::{set_syscall_stubs, SyscallStubs};
+        use std::sync::{Arc, Mutex};
+
+        struct LogCapture(Arc<Mutex<Vec<String>>>);
+
+        impl SyscallStubs for LogCapture {
+            fn sol_log(&self, message: &str) {
+                self.0.lock().unwrap().push(message.to_string());
+            }
+        }
+
+        let logs = Arc::new(Mutex::new(Vec::new()));
+        set_syscall_stubs(Box::new(LogCapture(logs.clone())));
+
+        let (_, proof, mut public_inputs) = load_receipt_and_extract_data();
+        let vk = load_verification_key();
+        public_inputs.inputs[1] = BASE_FIELD_MODULUS_Q;
+        public_inputs.inputs[3] = [0xFF; 32];
+
+        assert_eq!(invalid_public_input_indices(&public_inputs), vec![1, 3]);
+
+        let result = verify_proof_diagnostic(&proof, &public_inputs, &vk);
+        assert!(matches!(
+            result,
+            Err(ProgramError::Custom(code)) if code == Risc0SolanaError::InvalidPublicInput as u32
+        ));
+
+        let logs = logs.lock().unwrap();
+        for index in [1, 3] {
+            let expected = format!("Public input {} is not a valid scalar", index);
+            assert!(logs.contains(&expected), "Missing log: {}", expected);
+        }
+    }
+
+    #[test]
+    fn test_negate_g1_validates_point() {
+        let (_, proof, _) = load_receipt_and_extract_data();
+        let negated = negate_g1(&proof.pi_a).unwrap();
+        assert_eq!(negate_g1(&negated).unwrap(), proof.pi_a);
+        assert_eq!(negate_g1(&[0u8; 64]).unwrap(), [0u8; 64]);
+
+        let mut off_curve = proof.pi_a;
+        off_curve[63] ^= 1;
+        assert!(negate_g1(&off_curve).is_err());
+
+        let mut out_of_range = proof.pi_a;
+        out_of_range[..32].copy_from_slice(&BASE_FIELD_MODULUS_Q);
+        assert!(negate_g1(&out_of_range).is_err());
+
+        // Byte-swapped coordinates, as produced by an endianness mix-up.
+        let swapped = convert_endianness::<32, 64>(&proof.pi_a);
+        assert!(negate_g1(&swapped).is_err());
+    }
+
     #[test]
     fn test_base_field_modulus_against_reference() {
         use num_bigint::BigUint;
@@ -0,0 +1,41 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tagged struct digests used by the RISC Zero `ReceiptClaim` encoding.
+//!
+//! From: https://github.com/risc0/risc0/blob/v1.1.1/risc0/zkvm/src/receipt_claim.rs
+
+/// `sha256("risc0.Output")`
+pub const OUTPUT_TAG: [u8; 32] = [
+    0x77, 0xEA, 0xFE, 0xB3, 0x66, 0xA7, 0x8B, 0x47, 0x74, 0x7D, 0xE0, 0xD7, 0xBB, 0x17, 0x62, 0x84,
+    0x08, 0x5F, 0xF5, 0x56, 0x48, 0x87, 0x00, 0x9A, 0x5B, 0xE6, 0x3D, 0xA3, 0x2D, 0x35, 0x59, 0xD4,
+];
+
+/// `sha256("risc0.ReceiptClaim")`
+pub const RECEIPT_CLAIM_TAG: [u8; 32] = [
+    0xCB, 0x1F, 0xEF, 0xCD, 0x1F, 0x2D, 0x9A, 0x64, 0x97, 0x5C, 0xBB, 0xBF, 0x6E, 0x16, 0x1E, 0x29,
+    0x14, 0x43, 0x4B, 0x0C, 0xBB, 0x99, 0x60, 0xB8, 0x4D, 0xF5, 0xD7, 0x17, 0xE8, 0x6B, 0x48, 0xAF,
+];
+
+/// `sha256("risc0.SystemState")`
+pub const SYSTEM_STATE_TAG: [u8; 32] = [
+    0x20, 0x61, 0x15, 0xA8, 0x47, 0x20, 0x7C, 0x08, 0x92, 0xE0, 0xC0, 0x54, 0x72, 0x25, 0xDF, 0x31,
+    0xD0, 0x2A, 0x96, 0xEE, 0xB3, 0x95, 0x67, 0x0C, 0x31, 0x11, 0x2D, 0xFF, 0x90, 0xB4, 0x21, 0xD6,
+];
+
+/// Digest of the `SystemState` of a halted guest (pc = 0, merkle_root = 0).
+pub const SYSTEM_STATE_ZERO_DIGEST: [u8; 32] = [
+    0xA3, 0xAC, 0xC2, 0x71, 0x17, 0x41, 0x89, 0x96, 0x34, 0x0B, 0x84, 0xE5, 0xA9, 0x0F, 0x3E, 0xF4,
+    0xC4, 0x9D, 0x22, 0xC7, 0x9E, 0x44, 0xAA, 0xD8, 0x22, 0xEC, 0x9C, 0x31, 0x3E, 0x1E, 0xB8, 0xE2,
+];